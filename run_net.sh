@@ -0,0 +1,4 @@
+# Starts two instances that play map_coop_01.json together over loopback
+RUST_LOG="bot_rs=info" cargo run -- --mode run-game --log host.log --game-config assets/config/game_config.json --map map_coop_01.json --seed 1 --net-host --net-bind 127.0.0.1:7001 --net-peer 127.0.0.1:7002 &
+RUST_LOG="bot_rs=info" cargo run -- --mode run-game --log client.log --game-config assets/config/game_config.json --map map_coop_01.json --seed 1 --net-bind 127.0.0.1:7002 --net-peer 127.0.0.1:7001
+wait
@@ -3,5 +3,5 @@ pub mod ai_engine;
 pub mod chase_shoot_ai_engine;
 
 pub use ai_action::AiAction;
-pub use ai_engine::AiEngine;
+pub use ai_engine::{AiEngine, choose_weapon_by_range};
 pub use chase_shoot_ai_engine::ChaseShootAiEngine;
@@ -1,4 +1,5 @@
 use crate::ai::AiAction;
+use crate::config::WeaponConfig;
 use crate::game::{GameObj, components::WeaponComponent};
 use crate::game_utils::GameLib;
 use bevy::prelude::*;
@@ -15,4 +16,27 @@ pub trait AiEngine: Send + Sync {
     );
 
     fn cur_action(&self) -> AiAction;
+
+    fn weapon_index(&self) -> usize;
+}
+
+// Picks the weapon with the shortest range that still reaches the target,
+// or the longest-ranged one if none does. A range of None is unlimited.
+pub fn choose_weapon_by_range(weapon_configs: &[WeaponConfig], distance: f32) -> usize {
+    let range = |config: &WeaponConfig| config.range.unwrap_or(f32::INFINITY);
+
+    let in_range = weapon_configs
+        .iter()
+        .enumerate()
+        .filter(|(_, config)| range(config) >= distance)
+        .min_by(|(_, a), (_, b)| range(a).total_cmp(&range(b)));
+
+    let chosen = in_range.or_else(|| {
+        weapon_configs
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| range(a).total_cmp(&range(b)))
+    });
+
+    chosen.map(|(i, _)| i).unwrap_or(0)
 }
@@ -1,15 +1,16 @@
-use crate::ai::{AiAction, AiEngine};
+use crate::ai::{AiAction, AiEngine, choose_weapon_by_range};
 use crate::config::ChaseShootAiConfig;
 use crate::game::{GameObj, components::WeaponComponent};
 use crate::game_utils::GameLib;
-use crate::misc::get_rotation;
+use crate::misc::{game_rng, get_rotation};
 use bevy::prelude::*;
-use rand::{Rng, rng};
+use rand::Rng;
 use std::time::Duration;
 
 pub struct ChaseShootAiEngine {
     config: ChaseShootAiConfig,
     action: AiAction,
+    weapon_index: usize,
     action_timer: Timer,
     direction_keep_timer: Timer,
     directions: Vec<WeightedDirection>,
@@ -28,6 +29,7 @@ impl ChaseShootAiEngine {
         Self {
             config,
             action: AiAction::DoNothing,
+            weapon_index: 0,
             action_timer: Timer::from_seconds(0.0, TimerMode::Repeating),
             direction_keep_timer: Timer::from_seconds(0.0, TimerMode::Repeating),
             directions: Self::init_directions(),
@@ -78,23 +80,27 @@ impl ChaseShootAiEngine {
 
         Self::set_direction(obj, transform, (player_pos - obj.pos).normalize());
 
+        let Ok(config) = game_lib.get_game_obj_config(obj.config_index).bot_config() else {
+            return;
+        };
+
         match self.action {
             AiAction::Chase => {
-                let Ok(config) = game_lib.get_game_obj_config(obj.config_index).bot_config() else {
-                    return;
-                };
                 obj.speed = Some(config.speed);
+                weapon_comp.start_reload();
             }
             AiAction::Shoot => {
                 obj.speed = Some(0.0);
-                weapon_comp.fire_timer.reset();
+                weapon_comp.reset_fire_timers();
+                self.weapon_index =
+                    choose_weapon_by_range(&config.weapon_configs, obj.pos.distance(*player_pos));
             }
             _ => {}
         }
     }
 
     fn rand_action(config: &ChaseShootAiConfig) -> (AiAction, f32, f32) {
-        let mut r = rng();
+        let mut r = game_rng();
         if r.random_range(0.0..=1.0) < config.chase_prob {
             (
                 AiAction::Chase,
@@ -164,7 +170,7 @@ impl ChaseShootAiEngine {
     }
 
     fn choose_rand_direction(&self) -> Vec2 {
-        let mut r = rng();
+        let mut r = game_rng();
         let dice = r.random_range(0.0..TOTAL_WEIGHTS);
         let mut sum: f32 = 0.0;
         let mut idx = 0;
@@ -225,4 +231,8 @@ impl AiEngine for ChaseShootAiEngine {
     fn cur_action(&self) -> AiAction {
         self.action
     }
+
+    fn weapon_index(&self) -> usize {
+        self.weapon_index
+    }
 }
@@ -3,15 +3,22 @@ pub mod game_config;
 pub mod game_map_config;
 pub mod game_obj_config;
 pub mod gen_map_config;
+pub mod input_config;
 pub mod weapon_config;
 
 pub use ai_config::{AiConfig, ChaseShootAiConfig};
-pub use game_config::GameConfig;
+pub use game_config::{ControlScheme, GameConfig};
 pub use game_map_config::{GameMapConfig, GameMapObjConfig};
 pub use game_obj_config::{
-    BotConfig, DamageConfig, EnemySearchConfig, GameObjConfig, GameObjSide, GoodieConfig,
+    AccelerationConfig, ArmorConfig, BotConfig, BounceConfig, DamageConfig, DamageType,
+    DeviceConfig, DeviceKind, EnemySearchConfig, GameObjConfig, GameObjSide, GoodieConfig,
     GoodieEffectConfig, MissileConfig, MissileFeature, NamedGameObjConfig, OnDeathAction,
-    PierceConfig, PlayFrameConfig, SpawnMissileConfig, TileConfig,
+    PierceConfig, PlayFrameConfig, ShieldConfig, SpawnMissileConfig, SpawnerConfig, SplitConfig,
+    StatusEffectConfig, StatusEffectKind, TargetStrategy, TileConfig, WaveConfig,
 };
 pub use gen_map_config::{GenMapAlgorithmConfig, GenMapConfig, IslandGenMapAlgorithm};
-pub use weapon_config::{GunComponentConfig, GunConfig, WeaponConfig};
+pub use input_config::{InputAction, InputBinding, InputBindingConfig, WheelDirection};
+pub use weapon_config::{
+    BeamConfig, FirePattern, GunComponentConfig, GunConfig, MagazineConfig, TurretConfig,
+    WeaponConfig,
+};
@@ -12,9 +12,35 @@ pub struct GameConfig {
     image_config_file: String,
     gun_config_file: String,
     ai_config_file: String,
+    input_config_file: String,
     pub cell_size: f32,
     pub window_ext_size: f32,
     pub max_collide_span: f32,
+    pub knockback_decay: f32, // fraction of knockback velocity lost per second
+    pub control_scheme: ControlScheme,
+    pub camera: CameraConfig,
+    pub fog_of_war: Option<FogConfig>, // everything in the window is visible if None
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CameraConfig {
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub zoom_step: f32,   // zoom factor per wheel step
+    pub follow_rate: f32, // how fast the camera catches up with its target, per second
+    pub lead_time: f32,   // seconds of player movement the camera looks ahead
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FogConfig {
+    pub vision_range: f32,
+    pub blocked_by_tiles: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ControlScheme {
+    MouseSteer, // right click sets the heading, S stops
+    Keyboard,   // WASD or arrow keys move, the mouse aims
 }
 
 impl GameConfig {
@@ -62,4 +88,9 @@ impl GameConfig {
     pub fn ai_config_file(&self) -> PathBuf {
         self.config_dir().join(&self.ai_config_file)
     }
+
+    #[inline]
+    pub fn input_config_file(&self) -> PathBuf {
+        self.config_dir().join(&self.input_config_file)
+    }
 }
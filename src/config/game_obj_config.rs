@@ -3,6 +3,7 @@ use crate::game::GameObjType;
 use crate::misc::MyError;
 use bevy::prelude::*;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, PartialEq, Eq, Copy, Clone)]
 pub enum GameObjSide {
@@ -26,13 +27,34 @@ pub struct BotConfig {
     pub z: f32,
     pub side: GameObjSide,
     pub speed: f32,
+    pub acceleration: Option<f32>, // instant if None
+    pub deceleration: Option<f32>, // instant if None
+    pub turn_rate: Option<f32>,    // degrees per second, instant if None
     pub hp: f32,
     pub collide_span: f32,
-    pub weapon_config: WeaponConfig,
+    pub weapon_configs: Vec<WeaponConfig>,
     pub ai: Option<String>,
+    pub shield: Option<ShieldConfig>,
+    pub armor: Option<ArmorConfig>,
+    pub resistances: Option<HashMap<DamageType, f32>>, // fraction of damage resisted, negative for weakness
     pub on_death_actions: Vec<OnDeathAction>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShieldConfig {
+    pub image: String,
+    pub size: [f32; 2],
+    pub points: f32,
+    pub regen_delay: f32, // seconds after the last hit before regenerating
+    pub regen_rate: f32,  // points per second
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum ArmorConfig {
+    Flat(f32),
+    Percentage(f32),
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MissileConfig {
     pub image: String,
@@ -65,6 +87,48 @@ pub struct GoodieConfig {
     pub effect_config: GoodieEffectConfig,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceConfig {
+    pub image: String,
+    pub size: [f32; 2],
+    pub z: f32,
+    pub side: GameObjSide,
+    pub hp: f32,
+    pub collide_span: f32,
+    pub trigger_radius: f32,
+    pub arming_time: f32,
+    pub kind: DeviceKind,
+    pub on_death_actions: Vec<OnDeathAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnerConfig {
+    pub image: String,
+    pub size: [f32; 2],
+    pub z: f32,
+    pub waves: Vec<WaveConfig>,
+    pub spawn_interval: f32, // seconds between two bots of a wave
+    pub wave_cooldown: f32,  // seconds between the last bot of a wave and the next wave
+    pub max_alive: usize,    // holds back while this many AI bots are alive
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WaveConfig {
+    pub bots: Vec<SpawnBotConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnBotConfig {
+    pub bot: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum DeviceKind {
+    Mine,
+    Sentry(WeaponConfig),
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub enum OnDeathAction {
     DoDamage(DamageConfig),
@@ -74,10 +138,35 @@ pub enum OnDeathAction {
     DropGoodie(f32),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum DamageType {
+    Kinetic,
+    Explosive,
+    Energy,
+    Fire,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct DamageConfig {
     pub damage_range: f32,
     pub damage: f32,
+    pub damage_type: Option<DamageType>, // Explosive if None
+    pub knockback: Option<f32>,          // initial push speed away from the blast
+    pub effects: Option<Vec<StatusEffectConfig>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusEffectConfig {
+    pub kind: StatusEffectKind,
+    pub duration: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum StatusEffectKind {
+    Burn(f32), // damage per second
+    Slow(f32), // speed multiplier
+    Disarm,
+    Stun,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -90,18 +179,56 @@ pub struct SpawnMissileConfig {
 pub enum MissileFeature {
     Guided(EnemySearchConfig),
     Pierce(PierceConfig),
+    Bounce(BounceConfig),
+    Split(SplitConfig),
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct EnemySearchConfig {
     pub search_span: f32,
     pub search_wait_duration: f32,
+    pub turn_rate: Option<f32>, // degrees per second, instant if None
+    pub acceleration: Option<AccelerationConfig>,
+    pub lock_loss_angle: Option<f32>,            // degrees
+    pub target_strategy: Option<TargetStrategy>, // Random if None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum TargetStrategy {
+    Random,
+    Nearest,
+    LowestHp,
+    ClosestToHeading,
+    Marked, // the target marked by the player, falls back to Nearest
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccelerationConfig {
+    pub acceleration: f32,
+    pub max_speed: f32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct PierceConfig {
     pub max_pierce_count: usize,
     pub pierce_damage: f32,
+    pub damage_type: Option<DamageType>, // Kinetic if None
+    pub effects: Option<Vec<StatusEffectConfig>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BounceConfig {
+    pub max_bounce_count: usize,
+    pub speed_loss: f32, // fraction of speed lost on each bounce
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SplitConfig {
+    pub missile: String,
+    pub count: usize,
+    pub angle: f32, // cone angle in degrees
+    pub delay: Option<f32>,
+    pub trigger_range: Option<f32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -110,6 +237,7 @@ pub enum GoodieEffectConfig {
     SpeedBooster(f32),
     FastShooter(f32),
     HpFiller,
+    Ammo(usize),
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -119,6 +247,8 @@ pub enum GameObjConfig {
     Missile(MissileConfig),
     PlayFrame(PlayFrameConfig),
     Goodie(GoodieConfig),
+    Device(DeviceConfig),
+    Spawner(SpawnerConfig),
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -135,6 +265,8 @@ impl GameObjConfig {
             Self::PlayFrame(_) => (GameObjSide::Neutral, 0.0, GameObjType::PlayFrame),
             Self::Tile(cfg) => (GameObjSide::Neutral, cfg.collide_span, GameObjType::Tile),
             Self::Goodie(cfg) => (GameObjSide::Neutral, cfg.collide_span, GameObjType::Goodie),
+            Self::Device(cfg) => (cfg.side, cfg.collide_span, GameObjType::Device),
+            Self::Spawner(_) => (GameObjSide::Neutral, 0.0, GameObjType::Spawner),
         }
     }
 }
@@ -164,6 +296,18 @@ impl NamedGameObjConfig {
         }
     }
 
+    #[inline]
+    pub fn goodie_config(&self) -> Result<&GoodieConfig, MyError> {
+        match &self.config {
+            GameObjConfig::Goodie(config) => Ok(config),
+            _ => {
+                let msg = "Not a Goodie".to_string();
+                error!(msg);
+                Err(MyError::Other(msg))
+            }
+        }
+    }
+
     #[inline]
     pub fn tile_config(&self) -> Result<&TileConfig, MyError> {
         match &self.config {
@@ -176,10 +320,23 @@ impl NamedGameObjConfig {
         }
     }
 
+    #[inline]
+    pub fn spawner_config(&self) -> Result<&SpawnerConfig, MyError> {
+        match &self.config {
+            GameObjConfig::Spawner(config) => Ok(config),
+            _ => {
+                let msg = "Not a Spawner".to_string();
+                error!(msg);
+                Err(MyError::Other(msg))
+            }
+        }
+    }
+
     pub fn get_on_death_actions(&self) -> Result<&Vec<OnDeathAction>, MyError> {
         match &self.config {
             GameObjConfig::Bot(cfg) => Ok(&cfg.on_death_actions),
             GameObjConfig::Missile(cfg) => Ok(&cfg.on_death_actions),
+            GameObjConfig::Device(cfg) => Ok(&cfg.on_death_actions),
             _ => {
                 let msg = "There is no on-death actions".to_string();
                 error!(msg);
@@ -3,6 +3,7 @@ use serde::Deserialize;
 #[derive(Debug, Deserialize)]
 pub struct GenMapConfig {
     pub ai_bot_count: usize,
+    pub spawner_count: Option<usize>, // none if None
     pub row_count: usize,
     pub col_count: usize,
     pub algorithm: GenMapAlgorithmConfig,
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum InputAction {
+    Fire,
+    Stop,
+    Reload,
+    MoveTo,
+    QueueWaypoint, // held with MoveTo to queue the waypoint
+    MarkTarget,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    NextWeapon,
+    PrevWeapon,
+    SelectWeapon(usize),
+    ZoomIn,
+    ZoomOut,
+    ToggleMinimap,
+    Pause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    MouseWheel(WheelDirection),
+    GamepadButton(GamepadButton),
+    GamepadAxis(GamepadAxis, f32), // axis and the sign that triggers the action
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum WheelDirection {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputBindingConfig {
+    pub action: InputAction,
+    pub bindings: Vec<InputBinding>,
+}
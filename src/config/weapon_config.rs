@@ -1,23 +1,76 @@
+use crate::config::DamageType;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct GunConfig {
     pub image: String,
-    pub missile: String,
+    pub missile: Option<String>,
+    pub beam: Option<BeamConfig>,
+    pub device: Option<String>,
     pub size: [f32; 2],
     pub fire_point: [f32; 2],
     pub z: f32,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct BeamConfig {
+    pub image: String,
+    pub width: f32,
+    pub range: f32,
+    pub damage: f32,
+    pub damage_type: Option<DamageType>, // Energy if None
+    pub pierce_count: Option<usize>,     // bots hit after the first one
+    pub duration: f32,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct GunComponentConfig {
     pub config_name: String,
     pub pos: [f32; 2],
     pub direction: [f32; 2],
+    pub cooldown: Option<f32>,
+    pub initial_delay: Option<f32>,
+    pub pattern: Option<FirePattern>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum FirePattern {
+    Alternating(Vec<[f32; 2]>),
+    Burst(BurstConfig),
+    Spread(SpreadConfig),
+    Inaccurate(f32),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BurstConfig {
+    pub count: usize,
+    pub interval: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpreadConfig {
+    pub count: usize,
+    pub angle: f32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct WeaponConfig {
+    pub name: String,
+    pub range: Option<f32>,
     pub gun_components: Vec<GunComponentConfig>,
     pub fire_duration: f32,
+    pub magazine: Option<MagazineConfig>,
+    pub turret: Option<TurretConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TurretConfig {
+    pub turn_rate: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MagazineConfig {
+    pub size: usize,
+    pub reserve_ammo: Option<usize>,
+    pub reload_time: f32,
 }
@@ -1,27 +1,53 @@
+pub mod beam;
 pub mod create_obj;
+pub mod damage;
 pub mod game_obj;
+pub mod goodie;
 pub mod move_obj;
 pub mod on_death_action;
+pub mod path;
+pub mod player_input;
 pub mod playout;
 pub mod shoot;
+pub mod switch_weapon;
+pub mod target;
 
+pub use beam::fire_beam;
 pub use create_obj::{create_obj_by_config, create_obj_by_index};
-pub use game_obj::{GameObj, GameObjState, GameObjType, MapPos};
-pub use move_obj::{MoveResult, move_bot, move_missile, update_obj_pos};
+pub use damage::{DamageDealt, do_damage};
+pub use game_obj::{GameObj, GameObjState, GameObjType, MapPos, Shield};
+pub use goodie::capture_goodies;
+pub use move_obj::{MoveResult, move_bot, move_missile, push_bot, update_obj_pos};
 pub use on_death_action::on_death;
+pub use path::{find_path, is_blocked};
+pub use player_input::{PlayerInput, apply_player_input};
 pub use playout::{Phaseout, PlayFrame, Playout};
 pub use shoot::try_shoot;
+pub use switch_weapon::{WeaponSwitch, switch_weapon};
+pub use target::{find_marked_target, find_nearest_enemy};
 
 pub mod components {
     pub mod ai_comp;
+    pub mod beam_comp;
+    pub mod device_comp;
     pub mod markers;
     pub mod missile_comp;
+    pub mod path_comp;
     pub mod playout_comp;
+    pub mod shield_comp;
+    pub mod spawner_comp;
+    pub mod status_comp;
     pub mod weapon_comp;
 
     pub use ai_comp::AiComponent;
-    pub use markers::{AiBotComponent, InView, PlayerComponent, TileComponent};
-    pub use missile_comp::{EnemySearchAbility, MissileComponent, PierceAbility};
+    pub use beam_comp::BeamComponent;
+    pub use device_comp::DeviceComponent;
+    pub use markers::{AiBotComponent, InView, PeerComponent, PlayerComponent, TileComponent};
+    pub use missile_comp::{BounceAbility, EnemySearchAbility, MissileComponent, PierceAbility};
+    pub use path_comp::PathComponent;
     pub use playout_comp::PlayoutComponent;
-    pub use weapon_comp::WeaponComponent;
+    pub use shield_comp::ShieldComponent;
+    pub use spawner_comp::SpawnerComponent;
+    pub use status_comp::{StatusComponent, apply_status_effects};
+    pub use weapon_comp::{Magazine, WeaponComponent};
 }
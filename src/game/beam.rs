@@ -0,0 +1,190 @@
+use crate::config::{BeamConfig, DamageType};
+use crate::game::{GameObjType, components::BeamComponent, do_damage};
+use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use crate::misc::{AppState, MyError, get_rotation};
+use crate::obj_missing_from_lib;
+use bevy::prelude::*;
+
+const BEAM_Z: f32 = 1.5;
+
+pub fn fire_beam(
+    entity: Entity,
+    start: Vec2,
+    direction: Vec2,
+    config: &BeamConfig,
+    world_info: &WorldInfo,
+    game_map: &GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    commands: &mut Commands,
+) -> Result<(), MyError> {
+    let Some(side) = game_obj_lib.get(&entity).map(|obj| obj.side) else {
+        return obj_missing_from_lib!();
+    };
+    let range = clip_to_world(&start, &direction, config.range, world_info);
+    let max_hit_count = config.pierce_count.unwrap_or(0) + 1;
+    let mut hit_count = 0;
+    let mut end = range;
+
+    for (distance, e) in raycast(
+        entity,
+        &start,
+        &direction,
+        range,
+        game_map,
+        game_obj_lib,
+        game_lib,
+    ) {
+        let Some(obj) = game_obj_lib.get(&e) else {
+            continue;
+        };
+
+        if obj.obj_type == GameObjType::Tile {
+            end = distance;
+            break;
+        }
+
+        if !obj.is_damageable() || obj.side == side {
+            continue;
+        }
+
+        do_damage(
+            e,
+            config.damage,
+            config.damage_type.unwrap_or(DamageType::Energy),
+            false,
+            &[],
+            game_map,
+            game_obj_lib,
+            game_lib,
+            new_obj_queue,
+            commands,
+        )?;
+
+        hit_count += 1;
+        if hit_count >= max_hit_count {
+            end = distance;
+            break;
+        }
+    }
+
+    spawn_beam(
+        &start, &direction, end, config, world_info, game_lib, commands,
+    )
+}
+
+// Returns the collidable objects crossed by the ray, ordered by distance
+fn raycast(
+    entity: Entity,
+    start: &Vec2,
+    direction: &Vec2,
+    range: f32,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+) -> Vec<(f32, Entity)> {
+    let end = start + direction * range;
+    let max_collide_span = game_lib.game_config.max_collide_span;
+    let region = game_map.get_region(
+        start.x.min(end.x) - max_collide_span,
+        start.y.min(end.y) - max_collide_span,
+        start.x.max(end.x) + max_collide_span,
+        start.y.max(end.y) + max_collide_span,
+    );
+
+    let mut hits: Vec<(f32, u64, Entity)> = game_map
+        .map_iter(&region)
+        .filter(|e| *e != entity)
+        .filter_map(|e| {
+            let obj = game_obj_lib.get(&e)?;
+            if !obj.is_collidable() {
+                return None;
+            }
+            intersect_ray(start, direction, range, &obj.pos, obj.collide_span)
+                .map(|t| (t, obj.spawn_id, e))
+        })
+        .collect();
+    hits.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    hits.into_iter().map(|(t, _, e)| (t, e)).collect()
+}
+
+// Slab test of the ray against the square collide region of an object
+fn intersect_ray(
+    start: &Vec2,
+    direction: &Vec2,
+    range: f32,
+    pos: &Vec2,
+    collide_span: f32,
+) -> Option<f32> {
+    let mut t_min: f32 = 0.0;
+    let mut t_max = range;
+
+    for i in 0..2 {
+        let low = pos[i] - collide_span;
+        let high = pos[i] + collide_span;
+
+        if direction[i].abs() < f32::EPSILON {
+            if start[i] < low || start[i] > high {
+                return None;
+            }
+        } else {
+            let t1 = (low - start[i]) / direction[i];
+            let t2 = (high - start[i]) / direction[i];
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+
+    Some(t_min)
+}
+
+fn clip_to_world(start: &Vec2, direction: &Vec2, range: f32, world_info: &WorldInfo) -> f32 {
+    let bounds = [world_info.world_width(), world_info.world_height()];
+    let mut range = range;
+
+    for i in 0..2 {
+        if direction[i] > 0.0 {
+            range = range.min((bounds[i] - start[i]) / direction[i]);
+        } else if direction[i] < 0.0 {
+            range = range.min(-start[i] / direction[i]);
+        }
+    }
+
+    range.max(0.0)
+}
+
+fn spawn_beam(
+    start: &Vec2,
+    direction: &Vec2,
+    length: f32,
+    config: &BeamConfig,
+    world_info: &WorldInfo,
+    game_lib: &GameLib,
+    commands: &mut Commands,
+) -> Result<(), MyError> {
+    let image = game_lib.get_image(&config.image)?;
+    let pos = start + direction * length / 2.0;
+    let screen_pos = world_info.get_screen_pos(&pos);
+
+    commands.spawn((
+        DespawnOnExit(AppState::Game),
+        Sprite {
+            image,
+            custom_size: Some(Vec2::new(length, config.width)),
+            ..default()
+        },
+        Transform {
+            translation: Vec3::new(screen_pos.x, screen_pos.y, BEAM_Z),
+            rotation: get_rotation(direction),
+            ..default()
+        },
+        BeamComponent::new(pos, config.duration),
+    ));
+
+    Ok(())
+}
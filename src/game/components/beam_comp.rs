@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct BeamComponent {
+    pub pos: Vec2,
+    pub timer: Timer,
+}
+
+impl BeamComponent {
+    pub fn new(pos: Vec2, duration: f32) -> Self {
+        Self {
+            pos,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::config::DeviceConfig;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct DeviceComponent {
+    pub arming_timer: Timer,
+    pub trigger_radius: f32,
+}
+
+impl DeviceComponent {
+    pub fn new(config: &DeviceConfig) -> Self {
+        Self {
+            arming_timer: Timer::from_seconds(config.arming_time, TimerMode::Once),
+            trigger_radius: config.trigger_radius,
+        }
+    }
+}
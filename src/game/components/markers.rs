@@ -3,6 +3,9 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct PlayerComponent;
 
+#[derive(Component)]
+pub struct PeerComponent;
+
 #[derive(Component)]
 pub struct AiBotComponent;
 
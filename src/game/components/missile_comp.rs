@@ -1,11 +1,16 @@
-use crate::config::{EnemySearchConfig, MissileConfig, MissileFeature, PierceConfig};
-use crate::game::{GameObj, GameObjState, GameObjType, MoveResult, on_death, update_obj_pos};
+use crate::config::{
+    AccelerationConfig, BounceConfig, DamageType, EnemySearchConfig, MissileConfig, MissileFeature,
+    PierceConfig, SplitConfig, StatusEffectConfig, TargetStrategy,
+};
+use crate::game::{
+    GameObj, GameObjState, GameObjType, MoveResult, do_damage, on_death, update_obj_pos,
+};
 use crate::game_utils::{
-    DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, RectRegion, WorldInfo,
+    DespawnPool, GameLib, GameMap, GameObjLib, NewObj, NewObjQueue, RectRegion, WorldInfo,
 };
 use crate::misc::{
-    MyError, check_collide_bounds, check_collide_obj, check_collide_objs, get_collide_region,
-    get_rotation,
+    MyError, check_collide_bounds, check_collide_obj, check_collide_objs, game_rng,
+    get_collide_region, get_rotation,
 };
 use crate::obj_missing_from_lib;
 use bevy::prelude::*;
@@ -17,11 +22,18 @@ pub struct MissileComponent {
     pub alive_timer: Option<Timer>,
     pub enemy_search_ability: Option<EnemySearchAbility>,
     pub pierce_ability: Option<PierceAbility>,
+    pub bounce_ability: Option<BounceAbility>,
+    pub split_ability: Option<SplitAbility>,
+    pub owner: Option<Entity>, // the bot that fired it, whose mark guides it
 }
 
 pub struct EnemySearchAbility {
     search_timer: Timer,
     search_span: f32,
+    turn_rate: Option<f32>,
+    acceleration: Option<AccelerationConfig>,
+    lock_loss_angle: Option<f32>,
+    target_strategy: TargetStrategy,
     potential_targets: Vec<Entity>,
     cur_target: Option<Entity>,
     initial_search: bool,
@@ -32,14 +44,37 @@ pub struct PierceAbility {
     pierce_count: usize,
     max_pierce_count: usize,
     damage: f32,
+    damage_type: DamageType,
+    effects: Vec<StatusEffectConfig>,
+}
+
+pub struct BounceAbility {
+    bounce_count: usize,
+    max_bounce_count: usize,
+    speed_loss: f32,
+}
+
+pub struct SplitAbility {
+    missile_index: usize,
+    count: usize,
+    angle: f32,
+    delay_timer: Option<Timer>,
+    trigger_range: Option<f32>,
 }
 
 impl MissileComponent {
-    pub fn new(config: &MissileConfig) -> Self {
+    pub fn new(
+        config: &MissileConfig,
+        owner: Option<Entity>,
+        game_lib: &GameLib,
+    ) -> Result<Self, MyError> {
         let mut result = MissileComponent {
             alive_timer: None,
             enemy_search_ability: None,
             pierce_ability: None,
+            bounce_ability: None,
+            split_ability: None,
+            owner,
         };
 
         if let Some(alive_time) = config.alive_time {
@@ -54,10 +89,16 @@ impl MissileComponent {
                 MissileFeature::Pierce(cfg) => {
                     result.pierce_ability = Some(PierceAbility::new(cfg));
                 }
+                MissileFeature::Bounce(cfg) => {
+                    result.bounce_ability = Some(BounceAbility::new(cfg));
+                }
+                MissileFeature::Split(cfg) => {
+                    result.split_ability = Some(SplitAbility::new(cfg, game_lib)?);
+                }
             }
         }
 
-        result
+        Ok(result)
     }
 }
 
@@ -66,6 +107,10 @@ impl EnemySearchAbility {
         Self {
             search_timer: Timer::from_seconds(config.search_wait_duration, TimerMode::Repeating),
             search_span: config.search_span,
+            turn_rate: config.turn_rate.map(|turn_rate| turn_rate.to_radians()),
+            acceleration: config.acceleration.clone(),
+            lock_loss_angle: config.lock_loss_angle.map(|angle| angle.to_radians()),
+            target_strategy: config.target_strategy.unwrap_or(TargetStrategy::Random),
             potential_targets: Vec::new(),
             cur_target: None,
             initial_search: true,
@@ -78,25 +123,56 @@ impl EnemySearchAbility {
         transform: &mut Transform,
         game_map: &GameMap,
         game_obj_lib: &mut GameObjLib,
+        marked_target: Option<Entity>,
         time: &Time,
     ) -> Result<(), MyError> {
+        self.accelerate(entity, game_obj_lib, time)?;
+
         if let Some(target) = self.check_target_available(game_obj_lib)? {
-            self.update_with_target(entity, &target, transform, game_obj_lib)?;
+            self.update_with_target(entity, &target, transform, game_obj_lib, time)?;
         } else {
             if self.initial_search || self.search_timer.tick(time.delta()).is_finished() {
-                self.find_target(entity, transform, game_map, game_obj_lib)?;
+                self.find_target(
+                    entity,
+                    transform,
+                    game_map,
+                    game_obj_lib,
+                    marked_target,
+                    time,
+                )?;
             }
         }
 
         Ok(())
     }
 
+    fn accelerate(
+        &self,
+        entity: &Entity,
+        game_obj_lib: &mut GameObjLib,
+        time: &Time,
+    ) -> Result<(), MyError> {
+        let Some(acceleration) = self.acceleration.as_ref() else {
+            return Ok(());
+        };
+        let Some(obj) = game_obj_lib.get_mut(entity) else {
+            return obj_missing_from_lib!();
+        };
+
+        obj.speed = obj.speed.map(|speed| {
+            (speed + acceleration.acceleration * time.delta_secs()).min(acceleration.max_speed)
+        });
+
+        Ok(())
+    }
+
     fn update_with_target(
         &mut self,
         entity: &Entity,
         target: &Entity,
         transform: &mut Transform,
         game_obj_lib: &mut GameObjLib,
+        time: &Time,
     ) -> Result<(), MyError> {
         let Some(target_pos) = game_obj_lib.get(target).map(|o| o.pos) else {
             return obj_missing_from_lib!();
@@ -104,8 +180,23 @@ impl EnemySearchAbility {
         let Some(obj) = game_obj_lib.get_mut(entity) else {
             return obj_missing_from_lib!();
         };
+        let Some(target_direction) = (target_pos - obj.pos).try_normalize() else {
+            return Ok(());
+        };
 
-        obj.direction = (target_pos - obj.pos).normalize();
+        if let Some(lock_loss_angle) = self.lock_loss_angle
+            && obj.direction.angle_to(target_direction).abs() > lock_loss_angle
+        {
+            self.cur_target = None;
+            return Ok(());
+        }
+
+        obj.direction = match self.turn_rate {
+            Some(turn_rate) => obj
+                .direction
+                .rotate_towards(target_direction, turn_rate * time.delta_secs()),
+            None => target_direction,
+        };
         transform.rotation = get_rotation(&obj.direction);
 
         Ok(())
@@ -117,6 +208,8 @@ impl EnemySearchAbility {
         transform: &mut Transform,
         game_map: &GameMap,
         game_obj_lib: &mut GameObjLib,
+        marked_target: Option<Entity>,
+        time: &Time,
     ) -> Result<(), MyError> {
         let Some(obj) = game_obj_lib.get(entity).cloned() else {
             return obj_missing_from_lib!();
@@ -138,19 +231,20 @@ impl EnemySearchAbility {
             if obj2.state == GameObjState::Alive
                 && obj2.side != obj.side
                 && obj2.obj_type == GameObjType::Bot
-                && search_region.covers(&obj2.pos)
+                && obj.pos.distance(obj2.pos) <= self.search_span
+                && self.in_lock_angle(&obj, &obj2.pos)
             {
                 self.potential_targets.push(e);
             };
-
-            if obj2.side != obj.side && search_region.covers(&obj2.pos) {}
         }
+        self.potential_targets =
+            game_obj_lib.in_spawn_order(std::mem::take(&mut self.potential_targets).into_iter());
 
-        let mut r = rand::rng();
-        if let Some(target) = self.potential_targets.choose(&mut r).cloned() {
+        let target = self.choose_target(&obj, game_obj_lib, marked_target);
+        if let Some(target) = target {
             self.cur_target = Some(target);
             self.search_timer.reset();
-            self.update_with_target(entity, &target, transform, game_obj_lib)?;
+            self.update_with_target(entity, &target, transform, game_obj_lib, time)?;
         }
 
         self.initial_search = false;
@@ -158,6 +252,56 @@ impl EnemySearchAbility {
         Ok(())
     }
 
+    fn choose_target(
+        &self,
+        obj: &GameObj,
+        game_obj_lib: &GameObjLib,
+        marked_target: Option<Entity>,
+    ) -> Option<Entity> {
+        match self.target_strategy {
+            TargetStrategy::Random => self.potential_targets.choose(&mut *game_rng()).cloned(),
+            TargetStrategy::Nearest => {
+                self.min_target_by(game_obj_lib, |obj2| obj.pos.distance(obj2.pos))
+            }
+            TargetStrategy::LowestHp => {
+                self.min_target_by(game_obj_lib, |obj2| obj2.hp.unwrap_or(f32::MAX))
+            }
+            TargetStrategy::ClosestToHeading => self.min_target_by(game_obj_lib, |obj2| {
+                (obj2.pos - obj.pos)
+                    .try_normalize()
+                    .map_or(0.0, |d| obj.direction.angle_to(d).abs())
+            }),
+            TargetStrategy::Marked => marked_target
+                .filter(|target| self.potential_targets.contains(target))
+                .or_else(|| self.min_target_by(game_obj_lib, |obj2| obj.pos.distance(obj2.pos))),
+        }
+    }
+
+    fn min_target_by(
+        &self,
+        game_obj_lib: &GameObjLib,
+        key: impl Fn(&GameObj) -> f32,
+    ) -> Option<Entity> {
+        self.potential_targets
+            .iter()
+            .filter_map(|e| {
+                game_obj_lib
+                    .get(e)
+                    .map(|obj2| (key(obj2), obj2.spawn_id, *e))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+            .map(|(_, _, e)| e)
+    }
+
+    fn in_lock_angle(&self, obj: &GameObj, target_pos: &Vec2) -> bool {
+        let Some(lock_loss_angle) = self.lock_loss_angle else {
+            return true;
+        };
+        (target_pos - obj.pos)
+            .try_normalize()
+            .is_some_and(|d| obj.direction.angle_to(d).abs() <= lock_loss_angle)
+    }
+
     fn check_target_available(
         &mut self,
         game_obj_lib: &GameObjLib,
@@ -186,6 +330,8 @@ impl PierceAbility {
             pierce_count: 0,
             max_pierce_count: config.max_pierce_count,
             damage: config.pierce_damage,
+            damage_type: config.damage_type.unwrap_or(DamageType::Kinetic),
+            effects: config.effects.clone().unwrap_or_default(),
         }
     }
 
@@ -212,7 +358,7 @@ impl PierceAbility {
         };
         let new_pos = obj.pos + obj.direction * speed * time.delta_secs();
 
-        if !world_info.check_pos_visible(&new_pos) {
+        if !world_info.check_pos_simulated(&new_pos) {
             despawn_pool.add(entity, game_obj_lib)?;
             return Ok(MoveResult::NotMoved);
         }
@@ -316,7 +462,7 @@ impl PierceAbility {
                 continue;
             }
 
-            let Some(obj2) = game_obj_lib.get_mut(&e) else {
+            let Some(obj2) = game_obj_lib.get(&e) else {
                 continue;
             };
 
@@ -326,17 +472,19 @@ impl PierceAbility {
                 self.pierced_entities.insert(e);
                 collide = true;
 
-                if obj2.obj_type == GameObjType::Bot && obj2.side != obj.side {
-                    if let Some(hp) = obj2.hp {
-                        let new_hp = (hp - self.damage).max(0.0);
-                        obj2.hp = Some(new_hp);
-                        if new_hp <= 0.0 {
-                            on_death(e, game_map, game_obj_lib, game_lib, new_obj_queue, commands)?;
-                        }
-                    } else {
-                        error!("Bot's hp is None");
-                        continue;
-                    }
+                if obj2.is_damageable() && obj2.side != obj.side {
+                    do_damage(
+                        e,
+                        self.damage,
+                        self.damage_type,
+                        false,
+                        &self.effects,
+                        game_map,
+                        game_obj_lib,
+                        game_lib,
+                        new_obj_queue,
+                        commands,
+                    )?;
                 }
             }
         }
@@ -348,3 +496,123 @@ impl PierceAbility {
         Ok(())
     }
 }
+
+impl BounceAbility {
+    pub fn new(config: &BounceConfig) -> Self {
+        Self {
+            bounce_count: 0,
+            max_bounce_count: config.max_bounce_count,
+            speed_loss: config.speed_loss,
+        }
+    }
+
+    pub fn can_bounce(&self) -> bool {
+        self.bounce_count < self.max_bounce_count
+    }
+
+    pub fn bounce(
+        &mut self,
+        entity: Entity,
+        normal: Vec2,
+        transform: &mut Transform,
+        game_obj_lib: &mut GameObjLib,
+    ) -> Result<(), MyError> {
+        let Some(obj) = game_obj_lib.get_mut(&entity) else {
+            return obj_missing_from_lib!();
+        };
+
+        obj.direction = obj.direction - 2.0 * obj.direction.dot(normal) * normal;
+        obj.speed = obj
+            .speed
+            .map(|speed| speed * (1.0 - self.speed_loss).max(0.0));
+        transform.rotation = get_rotation(&obj.direction);
+        self.bounce_count += 1;
+
+        Ok(())
+    }
+}
+
+impl SplitAbility {
+    pub fn new(config: &SplitConfig, game_lib: &GameLib) -> Result<Self, MyError> {
+        Ok(Self {
+            missile_index: game_lib.get_game_obj_config_index(&config.missile)?,
+            count: config.count,
+            angle: config.angle.to_radians(),
+            delay_timer: config
+                .delay
+                .map(|delay| Timer::from_seconds(delay, TimerMode::Once)),
+            trigger_range: config.trigger_range,
+        })
+    }
+
+    // Returns true if the missile has been replaced by its children
+    pub fn update(
+        &mut self,
+        entity: Entity,
+        owner: Option<Entity>,
+        game_map: &GameMap,
+        game_obj_lib: &mut GameObjLib,
+        game_lib: &GameLib,
+        new_obj_queue: &mut NewObjQueue,
+        despawn_pool: &mut DespawnPool,
+        time: &Time,
+    ) -> Result<bool, MyError> {
+        let Some(obj) = game_obj_lib.get(&entity).cloned() else {
+            return obj_missing_from_lib!();
+        };
+
+        let delay_finished = self
+            .delay_timer
+            .as_mut()
+            .is_some_and(|timer| timer.tick(time.delta()).is_finished());
+        if !delay_finished && !self.check_enemy_near(&obj, game_map, game_obj_lib, game_lib) {
+            return Ok(false);
+        }
+
+        for i in 0..self.count {
+            let ratio = if self.count > 1 {
+                i as f32 / (self.count - 1) as f32 - 0.5
+            } else {
+                0.0
+            };
+            new_obj_queue.push(NewObj {
+                config_index: self.missile_index,
+                pos: obj.pos,
+                direction: Vec2::from_angle(self.angle * ratio).rotate(obj.direction),
+                speed: None,
+                owner,
+            });
+        }
+        despawn_pool.add(entity, game_obj_lib)?;
+
+        Ok(true)
+    }
+
+    fn check_enemy_near(
+        &self,
+        obj: &GameObj,
+        game_map: &GameMap,
+        game_obj_lib: &GameObjLib,
+        game_lib: &GameLib,
+    ) -> bool {
+        let Some(trigger_range) = self.trigger_range else {
+            return false;
+        };
+        let total_span = trigger_range + game_lib.game_config.max_collide_span;
+        let region = game_map.get_region(
+            obj.pos.x - total_span,
+            obj.pos.y - total_span,
+            obj.pos.x + total_span,
+            obj.pos.y + total_span,
+        );
+
+        game_map.map_iter(&region).any(|e| {
+            game_obj_lib.get(&e).is_some_and(|obj2| {
+                obj2.state == GameObjState::Alive
+                    && obj2.side != obj.side
+                    && obj2.obj_type == GameObjType::Bot
+                    && check_collide_obj(&obj.pos, trigger_range, &obj2.pos, obj2.collide_span)
+            })
+        })
+    }
+}
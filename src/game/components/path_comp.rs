@@ -0,0 +1,57 @@
+use crate::game::GameObj;
+use crate::misc::get_rotation;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+const ARRIVE_DISTANCE: f32 = 5.0;
+
+#[derive(Component, Default)]
+pub struct PathComponent {
+    waypoints: VecDeque<Vec2>,
+}
+
+impl PathComponent {
+    #[inline]
+    pub fn waypoints(&self) -> &VecDeque<Vec2> {
+        &self.waypoints
+    }
+
+    #[inline]
+    pub fn last(&self) -> Option<Vec2> {
+        self.waypoints.back().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.waypoints.clear();
+    }
+
+    pub fn extend(&mut self, waypoints: Vec<Vec2>) {
+        self.waypoints.extend(waypoints);
+    }
+
+    // Heads the bot toward the next waypoint and stops it after the last one
+    pub fn follow(&mut self, obj: &mut GameObj, transform: &mut Transform, speed: f32) {
+        if self.waypoints.is_empty() {
+            return;
+        }
+
+        while let Some(waypoint) = self.waypoints.front()
+            && obj.pos.distance(*waypoint) <= ARRIVE_DISTANCE
+        {
+            self.waypoints.pop_front();
+        }
+
+        match self.waypoints.front() {
+            Some(waypoint) => {
+                let direction = (waypoint - obj.pos).normalize();
+                obj.direction = direction;
+                obj.move_direction = None;
+                obj.speed = Some(speed);
+                transform.rotation = get_rotation(&direction);
+            }
+            None => {
+                obj.speed = Some(0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct ShieldComponent {
+    pub sprite: Entity,
+}
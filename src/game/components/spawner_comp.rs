@@ -0,0 +1,48 @@
+use crate::config::{SpawnerConfig, WaveConfig};
+use bevy::prelude::*;
+use std::hash::{Hash, Hasher};
+
+#[derive(Component)]
+pub struct SpawnerComponent {
+    pub wave_index: usize,
+    pub spawn_count: usize, // bots of the current wave spawned so far
+    pub spawn_timer: Timer,
+    pub cooldown_timer: Timer,
+}
+
+impl SpawnerComponent {
+    pub fn new(config: &SpawnerConfig) -> Self {
+        Self {
+            wave_index: 0,
+            spawn_count: 0,
+            spawn_timer: Timer::from_seconds(config.spawn_interval, TimerMode::Once),
+            cooldown_timer: Timer::from_seconds(config.wave_cooldown, TimerMode::Once),
+        }
+    }
+
+    // The name of the next bot of the current wave, None once all of them are spawned
+    pub fn next_bot<'a>(&self, wave: &'a WaveConfig) -> Option<&'a String> {
+        let mut index = self.spawn_count;
+        for spawn_bot in wave.bots.iter() {
+            if index < spawn_bot.count {
+                return Some(&spawn_bot.bot);
+            }
+            index -= spawn_bot.count;
+        }
+        None
+    }
+
+    pub fn next_wave(&mut self) {
+        self.wave_index += 1;
+        self.spawn_count = 0;
+        self.spawn_timer.reset();
+        self.cooldown_timer.reset();
+    }
+
+    pub fn hash_state(&self, hasher: &mut impl Hasher) {
+        self.wave_index.hash(hasher);
+        self.spawn_count.hash(hasher);
+        self.spawn_timer.elapsed().hash(hasher);
+        self.cooldown_timer.elapsed().hash(hasher);
+    }
+}
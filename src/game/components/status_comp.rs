@@ -0,0 +1,163 @@
+use crate::config::{StatusEffectConfig, StatusEffectKind};
+use bevy::prelude::*;
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+
+const BURN_TINT: Color = Color::srgb(1.0, 0.5, 0.3);
+const SLOW_TINT: Color = Color::srgb(0.5, 0.7, 1.0);
+const STUN_TINT: Color = Color::srgb(1.0, 1.0, 0.4);
+const DISARM_TINT: Color = Color::srgb(0.6, 0.6, 0.6);
+const HIT_FLASH_TINT: Color = Color::srgb(1.0, 0.3, 0.3);
+const HIT_FLASH_DURATION: f32 = 0.1;
+
+#[derive(Component, Default)]
+pub struct StatusComponent {
+    pub effects: Vec<StatusEffect>,
+    hit_flash: Option<Timer>,
+}
+
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub timer: Timer,
+}
+
+impl StatusComponent {
+    // An effect of a kind already active is refreshed instead of stacked
+    pub fn add(&mut self, config: &StatusEffectConfig) {
+        let timer = Timer::from_seconds(config.duration, TimerMode::Once);
+        match self
+            .effects
+            .iter_mut()
+            .find(|effect| discriminant(&effect.kind) == discriminant(&config.kind))
+        {
+            Some(effect) => {
+                effect.kind = config.kind;
+                effect.timer = timer;
+            }
+            None => self.effects.push(StatusEffect {
+                kind: config.kind,
+                timer,
+            }),
+        }
+    }
+
+    // Ticks the effects and returns the damage they dealt during this frame
+    pub fn update(&mut self, time: &Time) -> f32 {
+        let mut damage = 0.0;
+
+        for effect in self.effects.iter_mut() {
+            effect.timer.tick(time.delta());
+            if let StatusEffectKind::Burn(damage_per_sec) = effect.kind {
+                damage += damage_per_sec * time.delta_secs();
+            }
+        }
+        self.effects.retain(|effect| !effect.timer.is_finished());
+
+        if let Some(timer) = self.hit_flash.as_mut() {
+            timer.tick(time.delta());
+            if timer.is_finished() {
+                self.hit_flash = None;
+            }
+        }
+
+        damage
+    }
+
+    #[inline]
+    pub fn flash(&mut self) {
+        self.hit_flash = Some(Timer::from_seconds(HIT_FLASH_DURATION, TimerMode::Once));
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .map(|effect| match effect.kind {
+                StatusEffectKind::Slow(multiplier) => multiplier,
+                _ => 1.0,
+            })
+            .product()
+    }
+
+    #[inline]
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    #[inline]
+    pub fn is_disarmed(&self) -> bool {
+        self.has(StatusEffectKind::Disarm)
+    }
+
+    pub fn tint(&self) -> Color {
+        if self.hit_flash.is_some() {
+            return HIT_FLASH_TINT;
+        }
+
+        let priority = [
+            (StatusEffectKind::Stun, STUN_TINT),
+            (StatusEffectKind::Burn(0.0), BURN_TINT),
+            (StatusEffectKind::Slow(0.0), SLOW_TINT),
+            (StatusEffectKind::Disarm, DISARM_TINT),
+        ];
+
+        priority
+            .iter()
+            .find(|(kind, _)| self.has(*kind))
+            .map_or(Color::WHITE, |(_, color)| *color)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.effects
+            .iter()
+            .map(|effect| match effect.kind {
+                StatusEffectKind::Burn(_) => "Burning",
+                StatusEffectKind::Slow(_) => "Slowed",
+                StatusEffectKind::Disarm => "Disarmed",
+                StatusEffectKind::Stun => "Stunned",
+            })
+            .collect()
+    }
+
+    // Covers the effects the simulation depends on; the hit flash is only visual
+    pub fn hash_state(&self, hasher: &mut impl Hasher) {
+        for effect in self.effects.iter() {
+            discriminant(&effect.kind).hash(hasher);
+            match effect.kind {
+                StatusEffectKind::Burn(value) | StatusEffectKind::Slow(value) => {
+                    value.to_bits().hash(hasher)
+                }
+                StatusEffectKind::Disarm | StatusEffectKind::Stun => {}
+            }
+            effect.timer.elapsed().hash(hasher);
+        }
+    }
+
+    fn has(&self, kind: StatusEffectKind) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| discriminant(&effect.kind) == discriminant(&kind))
+    }
+}
+
+// Effects are applied through commands since damage is resolved on GameObjLib
+// without access to the target's components.
+pub fn apply_status_effects(
+    entity: Entity,
+    effects: &[StatusEffectConfig],
+    commands: &mut Commands,
+) {
+    if effects.is_empty() {
+        return;
+    }
+
+    let effects = effects.to_vec();
+    commands
+        .entity(entity)
+        .queue_silenced(move |mut entity: EntityWorldMut| {
+            if let Some(mut status_comp) = entity.get_mut::<StatusComponent>() {
+                for effect in effects.iter() {
+                    status_comp.add(effect);
+                }
+            }
+        });
+}
@@ -1,72 +1,371 @@
-use crate::config::WeaponConfig;
+use crate::config::{
+    BeamConfig, BotConfig, FirePattern, GunComponentConfig, MagazineConfig, TurretConfig,
+    WeaponConfig,
+};
 use crate::game_utils::GameLib;
-use crate::misc::{MyError, arr_to_vec2};
+use crate::misc::{MyError, arr_to_vec2, game_rng};
 use bevy::prelude::*;
+use rand::Rng;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
 #[derive(Component)]
 pub struct WeaponComponent {
+    pub weapon_index: usize,
+    pub guns: Vec<Entity>,
+    pub gun_states: Vec<GunState>,
+    pub magazines: Vec<Option<Magazine>>,
+    pub turret: Option<Turret>,
+}
+
+pub struct GunState {
+    pub pos: Vec2,
+    pub fire_point: Vec2,
+    pub fire_direction: Vec2,
+    pub missile_index: Option<usize>,
+    pub beam: Option<BeamConfig>,
+    pub device_index: Option<usize>,
+    pub cooldown: f32,
+    pub initial_delay: f32,
+    pub pattern: Option<FirePattern>,
     pub fire_timer: Timer,
-    pub fire_points: Vec<Vec2>,
-    pub fire_directions: Vec<Vec2>,
-    pub missile_indices: Vec<usize>,
+    pub shot_count: usize,
+}
+
+// The turret direction is relative to the hull, so the guns turn with the
+// bot and then swing back toward the target at the turn rate.
+#[derive(Debug, Clone)]
+pub struct Turret {
+    pub turn_rate: f32,
+    pub direction: Vec2,
+    pub target: Option<Vec2>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Magazine {
+    pub size: usize,
+    pub rounds: usize,
+    pub reserve: Option<usize>,
+    pub reload_timer: Timer,
+    pub reloading: bool,
 }
 
 impl WeaponComponent {
-    pub fn new(weapon_config: &WeaponConfig, game_lib: &GameLib) -> Result<Self, MyError> {
-        let fire_timer = Timer::from_seconds(weapon_config.fire_duration, TimerMode::Repeating);
-        let fire_points = Self::get_fire_points(weapon_config, game_lib)?;
-        let fire_directions = Self::get_fire_directions(weapon_config);
-        let missile_indices = Self::get_missile_indices(weapon_config, game_lib)?;
+    pub fn new(
+        weapon_index: usize,
+        weapon_config: &WeaponConfig,
+        guns: Vec<Entity>,
+        magazines: Vec<Option<Magazine>>,
+        game_lib: &GameLib,
+    ) -> Result<Self, MyError> {
+        let mut gun_states = Vec::new();
+
+        for gun_comp_config in weapon_config.gun_components.iter() {
+            let gun_state = GunState::new(gun_comp_config, weapon_config.fire_duration, game_lib)?;
+            gun_states.push(gun_state);
+        }
 
         Ok(Self {
-            fire_timer,
-            fire_points,
-            fire_directions,
-            missile_indices,
+            weapon_index,
+            guns,
+            gun_states,
+            magazines,
+            turret: weapon_config.turret.as_ref().map(Turret::new),
         })
     }
 
-    fn get_fire_points(
-        weapon_config: &WeaponConfig,
-        game_lib: &GameLib,
-    ) -> Result<Vec<Vec2>, MyError> {
-        let mut fire_points = Vec::new();
+    #[inline]
+    pub fn aim_direction(&self, hull_direction: Vec2) -> Vec2 {
+        match self.turret.as_ref() {
+            Some(turret) => hull_direction.rotate(turret.direction),
+            None => hull_direction,
+        }
+    }
 
-        for gun_comp_config in weapon_config.gun_components.iter() {
-            let gun_config = game_lib.get_gun_config(&gun_comp_config.config_name)?;
-            let gun_pos = arr_to_vec2(&gun_comp_config.pos);
-            let gun_direction = arr_to_vec2(&gun_comp_config.direction);
-            let local_fire_point = arr_to_vec2(&gun_config.fire_point);
-            let fire_point = gun_pos + gun_direction.rotate(local_fire_point);
-            fire_points.push(fire_point);
+    pub fn update_turret(&mut self, pos: Vec2, hull_direction: Vec2, time: &Time) {
+        if let Some(turret) = self.turret.as_mut() {
+            turret.update(pos, hull_direction, time);
         }
+    }
 
-        Ok(fire_points)
+    pub fn reset_fire_timers(&mut self) {
+        for gun_state in self.gun_states.iter_mut() {
+            gun_state.reset();
+        }
     }
 
-    fn get_fire_directions(weapon_config: &WeaponConfig) -> Vec<Vec2> {
-        let mut fire_directions = Vec::new();
+    pub fn new_magazines(config: &BotConfig) -> Vec<Option<Magazine>> {
+        config
+            .weapon_configs
+            .iter()
+            .map(|weapon_config| weapon_config.magazine.as_ref().map(Magazine::new))
+            .collect()
+    }
 
-        for gun_comp_config in weapon_config.gun_components.iter() {
-            let fire_direction = arr_to_vec2(&gun_comp_config.direction);
-            fire_directions.push(fire_direction);
+    #[inline]
+    pub fn magazine(&self) -> Option<&Magazine> {
+        self.magazines.get(self.weapon_index)?.as_ref()
+    }
+
+    #[inline]
+    pub fn magazine_mut(&mut self) -> Option<&mut Magazine> {
+        self.magazines.get_mut(self.weapon_index)?.as_mut()
+    }
+
+    // Weapons without a magazine never run out of ammo.
+    pub fn take_round(&mut self) -> bool {
+        let Some(magazine) = self.magazine_mut() else {
+            return true;
+        };
+
+        if magazine.reloading {
+            return false;
+        }
+        if magazine.is_empty() {
+            magazine.start_reload();
+            return false;
+        }
+
+        magazine.rounds -= 1;
+        if magazine.is_empty() {
+            magazine.start_reload();
         }
 
-        fire_directions
+        true
     }
 
-    fn get_missile_indices(
-        weapon_config: &WeaponConfig,
+    pub fn start_reload(&mut self) {
+        if let Some(magazine) = self.magazine_mut() {
+            magazine.start_reload();
+        }
+    }
+
+    pub fn update_reload(&mut self, time: &Time) {
+        if let Some(magazine) = self.magazine_mut() {
+            magazine.update_reload(time);
+        }
+    }
+
+    // Ammo goes to the current weapon, or to the first one that needs it if
+    // the current weapon has unlimited reserve ammo.
+    pub fn add_ammo(&mut self, amount: usize) {
+        let weapon_index = self.weapon_index;
+        let target = self
+            .magazines
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, magazine)| magazine.as_mut().map(|magazine| (i, magazine)))
+            .filter(|(_, magazine)| magazine.reserve.is_some())
+            .min_by_key(|(i, _)| *i != weapon_index);
+
+        if let Some((_, magazine)) = target {
+            magazine.reserve = magazine.reserve.map(|reserve| reserve + amount);
+        }
+    }
+
+    pub fn hash_state(&self, hasher: &mut impl Hasher) {
+        self.weapon_index.hash(hasher);
+        for gun_state in self.gun_states.iter() {
+            gun_state.fire_timer.duration().hash(hasher);
+            gun_state.fire_timer.elapsed().hash(hasher);
+            gun_state.shot_count.hash(hasher);
+        }
+        for magazine in self.magazines.iter().flatten() {
+            magazine.rounds.hash(hasher);
+            magazine.reserve.hash(hasher);
+            magazine.reloading.hash(hasher);
+            magazine.reload_timer.elapsed().hash(hasher);
+        }
+        if let Some(turret) = self.turret.as_ref() {
+            turret.direction.x.to_bits().hash(hasher);
+            turret.direction.y.to_bits().hash(hasher);
+        }
+    }
+}
+
+impl GunState {
+    pub fn new(
+        gun_comp_config: &GunComponentConfig,
+        fire_duration: f32,
         game_lib: &GameLib,
-    ) -> Result<Vec<usize>, MyError> {
-        let mut missile_indices = Vec::new();
+    ) -> Result<Self, MyError> {
+        let gun_config = game_lib.get_gun_config(&gun_comp_config.config_name)?;
+        let gun_pos = arr_to_vec2(&gun_comp_config.pos);
+        let fire_direction = arr_to_vec2(&gun_comp_config.direction);
+        let local_fire_point = arr_to_vec2(&gun_config.fire_point);
+        let fire_point = gun_pos + fire_direction.rotate(local_fire_point);
+        let missile_index = match gun_config.missile.as_ref() {
+            Some(missile) => Some(game_lib.get_game_obj_config_index(missile)?),
+            None => None,
+        };
+        let device_index = match gun_config.device.as_ref() {
+            Some(device) => Some(game_lib.get_game_obj_config_index(device)?),
+            None => None,
+        };
+        if missile_index.is_none() && gun_config.beam.is_none() && device_index.is_none() {
+            let msg = format!(
+                "Gun {} has no missile, beam or device",
+                gun_comp_config.config_name
+            );
+            error!(msg);
+            return Err(MyError::Other(msg));
+        }
+        let cooldown = gun_comp_config.cooldown.unwrap_or(fire_duration);
+        let initial_delay = gun_comp_config.initial_delay.unwrap_or(cooldown);
 
-        for gun_comp_config in weapon_config.gun_components.iter() {
-            let gun_config = game_lib.get_gun_config(&gun_comp_config.config_name)?;
-            let missile_index = game_lib.get_game_obj_config_index(&gun_config.missile)?;
-            missile_indices.push(missile_index);
+        Ok(Self {
+            pos: gun_pos,
+            fire_point,
+            fire_direction,
+            missile_index,
+            beam: gun_config.beam.clone(),
+            device_index,
+            cooldown,
+            initial_delay,
+            pattern: gun_comp_config.pattern.clone(),
+            fire_timer: Timer::from_seconds(initial_delay, TimerMode::Once),
+            shot_count: 0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.fire_timer
+            .set_duration(Duration::from_secs_f32(self.initial_delay));
+        self.fire_timer.reset();
+        self.shot_count = 0;
+    }
+
+    // Returns the fire points and directions, relative to the bot, of the
+    // missiles for one shot and restarts the timer for the next one.
+    pub fn fire(&mut self) -> Vec<(Vec2, Vec2)> {
+        let mut next_delay = self.cooldown;
+        let shots = match self.pattern.as_ref() {
+            None => vec![(self.fire_point, self.fire_direction)],
+            Some(FirePattern::Alternating(barrels)) => {
+                let barrel = barrels
+                    .get(self.shot_count % barrels.len().max(1))
+                    .map(arr_to_vec2)
+                    .unwrap_or(Vec2::ZERO);
+                let fire_point = self.fire_point + self.fire_direction.rotate(barrel);
+                vec![(fire_point, self.fire_direction)]
+            }
+            Some(FirePattern::Burst(config)) => {
+                if !(self.shot_count + 1).is_multiple_of(config.count.max(1)) {
+                    next_delay = config.interval;
+                }
+                vec![(self.fire_point, self.fire_direction)]
+            }
+            Some(FirePattern::Spread(config)) => (0..config.count)
+                .map(|i| {
+                    let ratio = if config.count > 1 {
+                        i as f32 / (config.count - 1) as f32 - 0.5
+                    } else {
+                        0.0
+                    };
+                    let angle = (config.angle * ratio).to_radians();
+                    (
+                        self.fire_point,
+                        Vec2::from_angle(angle).rotate(self.fire_direction),
+                    )
+                })
+                .collect(),
+            Some(FirePattern::Inaccurate(max_angle)) => {
+                let half_angle = max_angle.to_radians() / 2.0;
+                let angle = game_rng().random_range(-half_angle..=half_angle);
+                vec![(
+                    self.fire_point,
+                    Vec2::from_angle(angle).rotate(self.fire_direction),
+                )]
+            }
+        };
+
+        self.shot_count += 1;
+        self.fire_timer
+            .set_duration(Duration::from_secs_f32(next_delay));
+        self.fire_timer.reset();
+
+        shots
+    }
+}
+
+impl Turret {
+    pub fn new(config: &TurretConfig) -> Self {
+        Self {
+            turn_rate: config.turn_rate.to_radians(),
+            direction: Vec2::X,
+            target: None,
+        }
+    }
+
+    pub fn update(&mut self, pos: Vec2, hull_direction: Vec2, time: &Time) {
+        let Some(target_direction) = self
+            .target
+            .and_then(|target| (target - pos).try_normalize())
+        else {
+            return;
+        };
+
+        let hull_inverse = Vec2::new(hull_direction.x, -hull_direction.y);
+        let relative_target = hull_inverse.rotate(target_direction);
+        self.direction = self
+            .direction
+            .rotate_towards(relative_target, self.turn_rate * time.delta_secs());
+    }
+}
+
+impl Magazine {
+    pub fn new(config: &MagazineConfig) -> Self {
+        Self {
+            size: config.size,
+            rounds: config.size,
+            reserve: config.reserve_ammo,
+            reload_timer: Timer::from_seconds(config.reload_time, TimerMode::Once),
+            reloading: false,
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rounds == 0
+    }
+
+    #[inline]
+    pub fn can_reload(&self) -> bool {
+        !self.reloading && self.rounds < self.size && self.reserve != Some(0)
+    }
+
+    pub fn start_reload(&mut self) {
+        if self.can_reload() {
+            self.reloading = true;
+            self.reload_timer.reset();
+        }
+    }
+
+    pub fn cancel_reload(&mut self) {
+        self.reloading = false;
+    }
+
+    pub fn update_reload(&mut self, time: &Time) {
+        if !self.reloading {
+            return;
         }
 
-        Ok(missile_indices)
+        self.reload_timer.tick(time.delta());
+        if !self.reload_timer.is_finished() {
+            return;
+        }
+
+        let needed = self.size - self.rounds;
+        let loaded = match self.reserve {
+            Some(reserve) => {
+                let loaded = needed.min(reserve);
+                self.reserve = Some(reserve - loaded);
+                loaded
+            }
+            None => needed,
+        };
+
+        self.rounds += loaded;
+        self.reloading = false;
     }
 }
@@ -1,18 +1,22 @@
 use crate::config::{
-    BotConfig, GameMapObjConfig, GameObjConfig, GameObjSide, GoodieConfig, MissileConfig,
-    PlayFrameConfig, TileConfig, WeaponConfig,
+    BotConfig, DeviceConfig, DeviceKind, GameMapObjConfig, GameObjConfig, GameObjSide,
+    GoodieConfig, MissileConfig, PlayFrameConfig, ShieldConfig, SpawnerConfig, TileConfig,
+    WeaponConfig,
 };
 use crate::game::{
-    GameObj, GameObjState, PlayFrame,
+    GameObj, GameObjState, PlayFrame, Shield,
     components::{
-        AiBotComponent, AiComponent, InView, MissileComponent, PlayerComponent, PlayoutComponent,
-        TileComponent, WeaponComponent,
+        AiBotComponent, AiComponent, DeviceComponent, InView, Magazine, MissileComponent,
+        PathComponent, PeerComponent, PlayerComponent, PlayoutComponent, ShieldComponent,
+        SpawnerComponent, StatusComponent, TileComponent, WeaponComponent,
     },
 };
 use crate::game_utils::{GameInfo, GameLib, GameMap, GameObjLib, WorldInfo};
 use crate::misc::{AppState, MyError, arr_to_vec2, check_collide, get_rotation};
 use bevy::prelude::*;
 
+const SHIELD_Z: f32 = 0.1;
+
 pub fn create_obj_by_config(
     map_obj_config: &GameMapObjConfig,
     world_info: &WorldInfo,
@@ -31,6 +35,7 @@ pub fn create_obj_by_config(
         pos,
         direction,
         map_obj_config.speed,
+        None,
         world_info,
         game_map,
         game_obj_lib,
@@ -45,6 +50,7 @@ pub fn create_obj_by_index(
     pos: Vec2,
     direction: Vec2,
     speed: Option<f32>,
+    owner: Option<Entity>,
     world_info: &WorldInfo,
     game_map: &mut GameMap,
     game_obj_lib: &mut GameObjLib,
@@ -79,6 +85,7 @@ pub fn create_obj_by_index(
             pos,
             direction,
             speed,
+            owner,
             config,
             world_info,
             game_map,
@@ -124,6 +131,30 @@ pub fn create_obj_by_index(
             commands,
             game_info,
         ),
+        GameObjConfig::Device(config) => create_device(
+            config_index,
+            pos,
+            direction,
+            config,
+            world_info,
+            game_map,
+            game_obj_lib,
+            game_lib,
+            commands,
+            game_info,
+        ),
+        GameObjConfig::Spawner(config) => create_spawner(
+            config_index,
+            pos,
+            direction,
+            config,
+            world_info,
+            game_map,
+            game_obj_lib,
+            game_lib,
+            commands,
+            game_info,
+        ),
     }
 }
 
@@ -158,15 +189,33 @@ fn create_bot(
     let visible = world_info.check_pos_visible(&pos);
     let size = arr_to_vec2(&config.size);
     let entity = create_main_body(&config.image, size, visible, game_lib, commands)?;
-    let weapon_comp = create_weapon(entity, &config.weapon_config, game_lib, commands)?;
+    let magazines = WeaponComponent::new_magazines(config);
+    let weapon_comp = create_weapon(entity, 0, config, magazines, game_lib, commands)?;
+    let shield_sprite = match config.shield.as_ref() {
+        Some(shield_config) => Some(create_shield_sprite(shield_config, game_lib, commands)?),
+        None => None,
+    };
     let mut cmd = commands.entity(entity);
 
     cmd.insert(create_transform(&pos, &direction, config.z, world_info));
     cmd.insert(weapon_comp);
+    cmd.insert(StatusComponent::default());
+
+    if let Some(shield_sprite) = shield_sprite {
+        cmd.add_child(shield_sprite);
+        cmd.insert(ShieldComponent {
+            sprite: shield_sprite,
+        });
+    }
 
     match config.side {
         GameObjSide::Player => {
-            cmd.insert(PlayerComponent);
+            cmd.insert(PathComponent::default());
+            if game_info.is_next_player_local() {
+                cmd.insert(PlayerComponent);
+            } else {
+                cmd.insert(PeerComponent);
+            }
         }
         GameObjSide::Ai => {
             cmd.insert(AiBotComponent);
@@ -205,6 +254,7 @@ fn create_missile(
     pos: Vec2,
     direction: Vec2,
     speed: Option<f32>,
+    owner: Option<Entity>,
     config: &MissileConfig,
     world_info: &WorldInfo,
     game_map: &mut GameMap,
@@ -213,8 +263,8 @@ fn create_missile(
     commands: &mut Commands,
     game_info: &mut GameInfo,
 ) -> Result<(), MyError> {
-    if !world_info.check_pos_visible(&pos) {
-        return Ok(()); // Don't create missile if it is outside of player's view
+    if !world_info.check_pos_simulated(&pos) {
+        return Ok(()); // Don't create missile if it is outside of the simulation
     }
 
     if check_collide(
@@ -233,11 +283,12 @@ fn create_missile(
 
     let speed = speed.or(Some(config.speed));
     let size = arr_to_vec2(&config.size);
+    let missile_comp = MissileComponent::new(config, owner, game_lib)?;
     let entity = create_main_body(&config.image, size, true, game_lib, commands)?;
     let mut cmd = commands.entity(entity);
 
     cmd.insert(create_transform(&pos, &direction, config.z, world_info));
-    cmd.insert(MissileComponent::new(config));
+    cmd.insert(missile_comp);
 
     add_obj(
         entity,
@@ -270,7 +321,7 @@ fn create_play_frame(
     commands: &mut Commands,
     game_info: &mut GameInfo,
 ) -> Result<(), MyError> {
-    if !world_info.check_pos_visible(&pos) {
+    if !world_info.check_pos_simulated(&pos) {
         return Ok(());
     }
 
@@ -419,6 +470,124 @@ fn create_goodie(
     Ok(())
 }
 
+fn create_device(
+    config_index: usize,
+    pos: Vec2,
+    direction: Vec2,
+    device_config: &DeviceConfig,
+    world_info: &WorldInfo,
+    game_map: &mut GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    commands: &mut Commands,
+    game_info: &mut GameInfo,
+) -> Result<(), MyError> {
+    if check_collide(
+        None,
+        &pos,
+        device_config.collide_span,
+        game_lib.game_config.max_collide_span,
+        world_info,
+        game_map,
+        game_obj_lib,
+    ) {
+        let msg = "create_device failed because of collision".to_string();
+        error!(msg);
+        return Err(MyError::Other(msg));
+    }
+
+    let visible = world_info.check_pos_visible(&pos);
+    let size = arr_to_vec2(&device_config.size);
+    let entity = create_main_body(&device_config.image, size, visible, game_lib, commands)?;
+    let weapon_comp = match &device_config.kind {
+        DeviceKind::Sentry(weapon_config) => {
+            let guns = add_guns(entity, weapon_config, game_lib, commands)?;
+            let magazines = vec![weapon_config.magazine.as_ref().map(Magazine::new)];
+            Some(WeaponComponent::new(
+                0,
+                weapon_config,
+                guns,
+                magazines,
+                game_lib,
+            )?)
+        }
+        DeviceKind::Mine => None,
+    };
+    let mut cmd = commands.entity(entity);
+
+    cmd.insert(create_transform(
+        &pos,
+        &direction,
+        device_config.z,
+        world_info,
+    ));
+    cmd.insert(DeviceComponent::new(device_config));
+    if let Some(weapon_comp) = weapon_comp {
+        cmd.insert(weapon_comp);
+    }
+
+    add_obj(
+        entity,
+        config_index,
+        pos,
+        direction,
+        None,
+        Some(device_config.hp),
+        game_map,
+        game_obj_lib,
+        game_lib,
+        game_info,
+    );
+
+    debug!("Created Device {}", entity);
+
+    Ok(())
+}
+
+fn create_spawner(
+    config_index: usize,
+    pos: Vec2,
+    direction: Vec2,
+    spawner_config: &SpawnerConfig,
+    world_info: &WorldInfo,
+    game_map: &mut GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    commands: &mut Commands,
+    game_info: &mut GameInfo,
+) -> Result<(), MyError> {
+    let visible = world_info.check_pos_visible(&pos);
+    let size = arr_to_vec2(&spawner_config.size);
+    let entity = create_main_body(&spawner_config.image, size, visible, game_lib, commands)?;
+    let mut cmd = commands.entity(entity);
+
+    cmd.insert(create_transform(
+        &pos,
+        &direction,
+        spawner_config.z,
+        world_info,
+    ));
+    cmd.insert(SpawnerComponent::new(spawner_config));
+    game_info.add_waves(spawner_config.waves.len());
+
+    add_obj(
+        entity,
+        config_index,
+        pos,
+        direction,
+        None,
+        None,
+        game_map,
+        game_obj_lib,
+        game_lib,
+        game_info,
+    );
+
+    debug!("Created Spawner {}", entity);
+
+    Ok(())
+}
+
 fn create_main_body(
     image_name: &String,
     size: Vec2,
@@ -448,6 +617,28 @@ fn create_main_body(
     Ok(entity)
 }
 
+fn create_shield_sprite(
+    shield_config: &ShieldConfig,
+    game_lib: &GameLib,
+    commands: &mut Commands,
+) -> Result<Entity, MyError> {
+    let image = game_lib.get_image(&shield_config.image)?;
+    let shield_sprite = commands
+        .spawn((
+            DespawnOnExit(AppState::Game),
+            Sprite {
+                image,
+                custom_size: Some(arr_to_vec2(&shield_config.size)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, SHIELD_Z),
+            Visibility::Inherited,
+        ))
+        .id();
+
+    Ok(shield_sprite)
+}
+
 fn create_transform(pos: &Vec2, direction: &Vec2, z: f32, world_info: &WorldInfo) -> Transform {
     let screen_pos = world_info.get_screen_pos(pos);
     Transform {
@@ -457,15 +648,23 @@ fn create_transform(pos: &Vec2, direction: &Vec2, z: f32, world_info: &WorldInfo
     }
 }
 
-fn create_weapon(
+pub fn create_weapon(
     main_body: Entity,
-    weapon_config: &WeaponConfig,
+    weapon_index: usize,
+    config: &BotConfig,
+    magazines: Vec<Option<Magazine>>,
     game_lib: &GameLib,
     commands: &mut Commands,
 ) -> Result<WeaponComponent, MyError> {
-    add_guns(main_body, weapon_config, game_lib, commands)?;
+    let Some(weapon_config) = config.weapon_configs.get(weapon_index) else {
+        let msg = format!("Cannot find weapon {} of Bot", weapon_index);
+        error!(msg);
+        return Err(MyError::NotFound(msg));
+    };
+
+    let guns = add_guns(main_body, weapon_config, game_lib, commands)?;
 
-    let weapon_comp = WeaponComponent::new(weapon_config, game_lib)?;
+    let weapon_comp = WeaponComponent::new(weapon_index, weapon_config, guns, magazines, game_lib)?;
 
     Ok(weapon_comp)
 }
@@ -475,7 +674,9 @@ fn add_guns(
     weapon_config: &WeaponConfig,
     game_lib: &GameLib,
     commands: &mut Commands,
-) -> Result<(), MyError> {
+) -> Result<Vec<Entity>, MyError> {
+    let mut guns = Vec::new();
+
     for gun_comp_config in weapon_config.gun_components.iter() {
         let gun_config = game_lib.get_gun_config(&gun_comp_config.config_name)?;
         let gun_img = game_lib.get_image(&gun_config.image)?;
@@ -503,9 +704,10 @@ fn add_guns(
             .id();
 
         commands.entity(main_body).add_child(gun);
+        guns.push(gun);
     }
 
-    Ok(())
+    Ok(guns)
 }
 
 fn add_obj(
@@ -520,11 +722,14 @@ fn add_obj(
     game_lib: &GameLib,
     game_info: &mut GameInfo,
 ) {
-    let (side, collide_span, obj_type) = game_lib
-        .get_game_obj_config(config_index)
-        .config
-        .basic_info();
+    let config = &game_lib.get_game_obj_config(config_index).config;
+    let (side, collide_span, obj_type) = config.basic_info();
+    let shield = match config {
+        GameObjConfig::Bot(bot_config) => bot_config.shield.as_ref().map(Shield::new),
+        _ => None,
+    };
     let obj = GameObj {
+        spawn_id: game_obj_lib.next_spawn_id(),
         config_index,
         pos,
         direction,
@@ -533,6 +738,10 @@ fn add_obj(
         collide_span,
         speed,
         hp,
+        shield,
+        move_direction: None,
+        move_velocity: Vec2::ZERO,
+        velocity: Vec2::ZERO,
         obj_type,
         state: GameObjState::Alive,
     };
@@ -540,7 +749,7 @@ fn add_obj(
     if obj.is_ai_bot() {
         game_info.incr_ai_bot_count();
     } else if obj.is_player() {
-        game_info.set_player(entity);
+        game_info.add_player(entity);
     }
 
     game_map.add(&obj.map_pos, entity);
@@ -0,0 +1,105 @@
+use crate::config::{ArmorConfig, DamageType, GameObjConfig, StatusEffectConfig};
+use crate::game::{components::apply_status_effects, on_death};
+use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObjQueue};
+use crate::misc::MyError;
+use crate::obj_missing_from_lib;
+use bevy::prelude::*;
+
+// Written by do_damage for the combat feedback, which is not part of the simulation
+#[derive(Message)]
+pub struct DamageDealt {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub damage: f32,
+}
+
+// All damage goes through here: resistance first, then shield, then armor, then hp.
+// Flat armor is taken off each hit, so it is skipped for the per-frame slices of
+// damage over time. The damage taken by the shield and hp together is reported
+// as DamageDealt
+pub fn do_damage(
+    entity: Entity,
+    damage: f32,
+    damage_type: DamageType,
+    over_time: bool,
+    effects: &[StatusEffectConfig],
+    game_map: &GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    commands: &mut Commands,
+) -> Result<(), MyError> {
+    let Some(obj) = game_obj_lib.get_mut(&entity) else {
+        return obj_missing_from_lib!();
+    };
+
+    if !obj.is_damageable() {
+        return Ok(());
+    }
+
+    let Some(hp) = obj.hp else {
+        let msg = format!("GameObj {}'s hp is None", entity);
+        error!(msg);
+        return Err(MyError::Other(msg));
+    };
+
+    let bot_config = match &game_lib.get_game_obj_config(obj.config_index).config {
+        GameObjConfig::Bot(bot_config) => Some(bot_config),
+        _ => None,
+    };
+
+    let mut damage = damage;
+    if let Some(resistance) = bot_config
+        .and_then(|cfg| cfg.resistances.as_ref())
+        .and_then(|resistances| resistances.get(&damage_type))
+    {
+        damage *= (1.0 - resistance).max(0.0);
+    }
+
+    let mut absorbed = 0.0;
+    if let Some(shield) = obj.shield.as_mut() {
+        let remaining = shield.absorb(damage);
+        absorbed = damage - remaining;
+        damage = remaining;
+        if damage <= 0.0 {
+            write_damage_dealt(entity, obj.pos, absorbed, commands);
+            return Ok(());
+        }
+    }
+
+    if let Some(armor) = bot_config.and_then(|cfg| cfg.armor) {
+        damage = match armor {
+            ArmorConfig::Flat(_) if over_time => damage,
+            ArmorConfig::Flat(value) => (damage - value).max(0.0),
+            ArmorConfig::Percentage(value) => damage * (1.0 - value / 100.0).clamp(0.0, 1.0),
+        };
+    }
+
+    let new_hp = (hp - damage).max(0.0);
+    let dealt = absorbed + hp - new_hp;
+    obj.hp = Some(new_hp);
+    write_damage_dealt(entity, obj.pos, dealt, commands);
+    apply_status_effects(entity, effects, commands);
+    if new_hp == 0.0 {
+        on_death(
+            entity,
+            game_map,
+            game_obj_lib,
+            game_lib,
+            new_obj_queue,
+            commands,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_damage_dealt(entity: Entity, pos: Vec2, damage: f32, commands: &mut Commands) {
+    if damage > 0.0 {
+        commands.write_message(DamageDealt {
+            entity,
+            pos,
+            damage,
+        });
+    }
+}
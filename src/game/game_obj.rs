@@ -1,5 +1,6 @@
-use crate::config::GameObjSide;
+use crate::config::{GameObjSide, ShieldConfig};
 use bevy::prelude::*;
+use std::hash::{Hash, Hasher};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 pub struct MapPos {
@@ -9,6 +10,7 @@ pub struct MapPos {
 
 #[derive(Debug, Copy, Clone)]
 pub struct GameObj {
+    pub spawn_id: u64, // the same on every lockstep instance, unlike the Entity
     pub config_index: usize,
     pub pos: Vec2,
     pub map_pos: MapPos,
@@ -18,9 +20,22 @@ pub struct GameObj {
     pub collide_span: f32,
     pub speed: Option<f32>,
     pub hp: Option<f32>,
+    pub shield: Option<Shield>,
+    pub move_direction: Option<Vec2>, // heading independent of facing, direction if None
+    pub move_velocity: Vec2,          // steering, integrated toward the heading * speed
+    pub velocity: Vec2,               // knockback, separate from steering speed
     pub obj_type: GameObjType,
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct Shield {
+    pub points: f32,
+    pub max_points: f32,
+    regen_delay: f32,
+    regen_rate: f32,
+    delay_remaining: f32,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum GameObjType {
     Bot,
@@ -28,6 +43,8 @@ pub enum GameObjType {
     Missile,
     PlayFrame,
     Goodie,
+    Device,
+    Spawner,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -42,7 +59,15 @@ impl GameObj {
     pub fn is_collidable(&self) -> bool {
         self.collide_span > 0.0
             && self.state == GameObjState::Alive
-            && (self.obj_type == GameObjType::Bot || self.obj_type == GameObjType::Tile)
+            && (self.obj_type == GameObjType::Bot
+                || self.obj_type == GameObjType::Tile
+                || self.obj_type == GameObjType::Device)
+    }
+
+    #[inline]
+    pub fn is_damageable(&self) -> bool {
+        self.state == GameObjState::Alive
+            && (self.obj_type == GameObjType::Bot || self.obj_type == GameObjType::Device)
     }
 
     #[inline]
@@ -62,3 +87,41 @@ impl GameObj {
             || self.obj_type == GameObjType::PlayFrame
     }
 }
+
+impl Shield {
+    pub fn new(config: &ShieldConfig) -> Self {
+        Self {
+            points: config.points,
+            max_points: config.points,
+            regen_delay: config.regen_delay,
+            regen_rate: config.regen_rate,
+            delay_remaining: 0.0,
+        }
+    }
+
+    // Returns the damage left after the shield has absorbed what it can
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let absorbed = damage.min(self.points);
+        self.points -= absorbed;
+        self.delay_remaining = self.regen_delay;
+        damage - absorbed
+    }
+
+    pub fn update(&mut self, time: &Time) {
+        if self.delay_remaining > 0.0 {
+            self.delay_remaining -= time.delta_secs();
+        } else {
+            self.points = (self.points + self.regen_rate * time.delta_secs()).min(self.max_points);
+        }
+    }
+
+    #[inline]
+    pub fn is_up(&self) -> bool {
+        self.points > 0.0
+    }
+
+    pub fn hash_state(&self, hasher: &mut impl Hasher) {
+        self.points.to_bits().hash(hasher);
+        self.delay_remaining.to_bits().hash(hasher);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::config::GoodieEffectConfig;
+use crate::game::{GameObjState, GameObjType, components::WeaponComponent};
+use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib};
+use crate::misc::{MyError, check_collide_obj};
+use bevy::prelude::*;
+
+pub fn capture_goodies(
+    pos: &Vec2,
+    collide_span: f32,
+    weapon_comp: &mut WeaponComponent,
+    game_map: &GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    despawn_pool: &mut DespawnPool,
+) -> Result<(), MyError> {
+    let total_span = collide_span + game_lib.game_config.max_collide_span;
+    let region = game_map.get_region(
+        pos.x - total_span,
+        pos.y - total_span,
+        pos.x + total_span,
+        pos.y + total_span,
+    );
+
+    for entity in game_map.map_iter(&region) {
+        if let Some(obj) = game_obj_lib.get(&entity)
+            && obj.obj_type == GameObjType::Goodie
+            && obj.state == GameObjState::Alive
+            && check_collide_obj(pos, collide_span, &obj.pos, obj.collide_span)
+        {
+            let config = game_lib
+                .get_game_obj_config(obj.config_index)
+                .goodie_config()?;
+            apply_goodie_effect(&config.effect_config, weapon_comp);
+            despawn_pool.add(entity, game_obj_lib)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_goodie_effect(effect_config: &GoodieEffectConfig, weapon_comp: &mut WeaponComponent) {
+    if let GoodieEffectConfig::Ammo(amount) = effect_config {
+        weapon_comp.add_ammo(*amount);
+    }
+}
@@ -1,15 +1,21 @@
-use crate::config::GameObjSide;
-use crate::game::{GameObjState, GameObjType, components::InView, on_death};
+use crate::config::{BotConfig, GameObjSide};
+use crate::game::{
+    GameObj, GameObjState, GameObjType,
+    components::{BounceAbility, InView},
+    on_death,
+};
 use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
-use crate::misc::{MyError, check_collide, check_collide_obj};
+use crate::misc::{MyError, check_collide, check_collide_obj, get_contact};
 use crate::obj_missing_from_lib;
 use bevy::prelude::*;
-use std::collections::HashSet;
+
+const MIN_KNOCKBACK_SPEED: f32 = 1.0;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MoveResult {
     Moved(Vec2),
     Collided,
+    Bounced,
     NotMoved,
 }
 
@@ -27,14 +33,185 @@ pub fn move_bot(
     commands: &mut Commands,
     time: &Time,
 ) -> Result<MoveResult, MyError> {
-    if speed == 0.0 {
+    let Some(obj) = game_obj_lib.get(&entity).cloned() else {
+        return obj_missing_from_lib!();
+    };
+
+    if speed == 0.0 && obj.move_velocity == Vec2::ZERO {
+        return Ok(MoveResult::NotMoved);
+    }
+
+    let config = game_lib
+        .get_game_obj_config(obj.config_index)
+        .bot_config()?;
+    let move_velocity = steer(&obj, speed, config, time);
+    if move_velocity == Vec2::ZERO {
+        if let Some(obj) = game_obj_lib.get_mut(&entity) {
+            obj.move_velocity = Vec2::ZERO;
+        }
         return Ok(MoveResult::NotMoved);
     }
 
+    let move_velocity = slide(
+        entity,
+        &obj,
+        move_velocity,
+        world_info,
+        game_map,
+        game_obj_lib,
+        game_lib,
+        time,
+    );
+    let new_pos = obj.pos + move_velocity * time.delta_secs();
+    let result = move_bot_to(
+        entity,
+        &obj,
+        new_pos,
+        transform,
+        visibility,
+        world_info,
+        game_map,
+        game_obj_lib,
+        game_lib,
+        new_obj_queue,
+        despawn_pool,
+        commands,
+    )?;
+
+    if let Some(obj) = game_obj_lib.get_mut(&entity) {
+        obj.move_velocity = match result {
+            MoveResult::Collided => Vec2::ZERO,
+            _ => move_velocity,
+        };
+    }
+
+    Ok(result)
+}
+
+// Turns and accelerates the current velocity toward the desired heading and speed
+fn steer(obj: &GameObj, speed: f32, config: &BotConfig, time: &Time) -> Vec2 {
+    let delta = time.delta_secs();
+    let cur_speed = obj.move_velocity.length();
+    let target_heading = obj.move_direction.unwrap_or(obj.direction);
+    let heading = match config.turn_rate {
+        Some(turn_rate) => obj
+            .move_velocity
+            .try_normalize()
+            .unwrap_or(target_heading)
+            .rotate_towards(target_heading, turn_rate.to_radians() * delta),
+        None => target_heading,
+    };
+    let new_speed = if speed > cur_speed {
+        config.acceleration.map_or(speed, |acceleration| {
+            (cur_speed + acceleration * delta).min(speed)
+        })
+    } else {
+        config.deceleration.map_or(speed, |deceleration| {
+            (cur_speed - deceleration * delta).max(speed)
+        })
+    };
+
+    heading * new_speed
+}
+
+// When the move is blocked, keeps the velocity component along the wall
+fn slide(
+    entity: Entity,
+    obj: &GameObj,
+    velocity: Vec2,
+    world_info: &WorldInfo,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+    time: &Time,
+) -> Vec2 {
+    let (major, minor) = if velocity.x.abs() >= velocity.y.abs() {
+        (Vec2::new(velocity.x, 0.0), Vec2::new(0.0, velocity.y))
+    } else {
+        (Vec2::new(0.0, velocity.y), Vec2::new(velocity.x, 0.0))
+    };
+
+    [velocity, major, minor]
+        .into_iter()
+        .filter(|v| *v != Vec2::ZERO)
+        .find(|v| {
+            !check_collide(
+                Some(entity),
+                &(obj.pos + v * time.delta_secs()),
+                obj.collide_span,
+                game_lib.game_config.max_collide_span,
+                world_info,
+                game_map,
+                game_obj_lib,
+            )
+        })
+        .unwrap_or(velocity)
+}
+
+// Moves the bot by its knockback velocity, which decays over time
+pub fn push_bot(
+    entity: Entity,
+    transform: &mut Transform,
+    visibility: &mut Visibility,
+    world_info: &WorldInfo,
+    game_map: &mut GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    despawn_pool: &mut DespawnPool,
+    commands: &mut Commands,
+    time: &Time,
+) -> Result<MoveResult, MyError> {
     let Some(obj) = game_obj_lib.get(&entity).cloned() else {
         return obj_missing_from_lib!();
     };
-    let new_pos = obj.pos + obj.direction * speed * time.delta_secs();
+
+    if obj.velocity == Vec2::ZERO {
+        return Ok(MoveResult::NotMoved);
+    }
+
+    let new_pos = obj.pos + obj.velocity * time.delta_secs();
+    let result = move_bot_to(
+        entity,
+        &obj,
+        new_pos,
+        transform,
+        visibility,
+        world_info,
+        game_map,
+        game_obj_lib,
+        game_lib,
+        new_obj_queue,
+        despawn_pool,
+        commands,
+    )?;
+
+    if let Some(obj) = game_obj_lib.get_mut(&entity) {
+        let decay = (1.0 - game_lib.game_config.knockback_decay * time.delta_secs()).max(0.0);
+        obj.velocity = match result {
+            MoveResult::Collided => Vec2::ZERO,
+            _ if obj.velocity.length() * decay < MIN_KNOCKBACK_SPEED => Vec2::ZERO,
+            _ => obj.velocity * decay,
+        };
+    }
+
+    Ok(result)
+}
+
+fn move_bot_to(
+    entity: Entity,
+    obj: &GameObj,
+    new_pos: Vec2,
+    transform: &mut Transform,
+    visibility: &mut Visibility,
+    world_info: &WorldInfo,
+    game_map: &mut GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    despawn_pool: &mut DespawnPool,
+    commands: &mut Commands,
+) -> Result<MoveResult, MyError> {
     let collided = check_collide(
         Some(entity),
         &new_pos,
@@ -56,19 +233,9 @@ pub fn move_bot(
         )?;
 
         match obj.side {
-            GameObjSide::Ai => {
+            GameObjSide::Ai | GameObjSide::Player => {
                 update_bot_visibility(entity, &new_pos, visibility, world_info, commands);
             }
-            GameObjSide::Player => {
-                capture_goodies(
-                    &new_pos,
-                    obj.collide_span,
-                    game_map,
-                    game_obj_lib,
-                    game_lib,
-                    despawn_pool,
-                )?;
-            }
             _ => {}
         }
     }
@@ -95,6 +262,7 @@ pub fn move_bot(
 pub fn move_missile(
     entity: Entity,
     speed: f32,
+    bounce_ability: Option<&mut BounceAbility>,
     transform: &mut Transform,
     world_info: &WorldInfo,
     game_map: &mut GameMap,
@@ -114,12 +282,12 @@ pub fn move_missile(
     };
     let new_pos = obj.pos + obj.direction * speed * time.delta_secs();
 
-    if !world_info.check_pos_visible(&new_pos) {
+    if !world_info.check_pos_simulated(&new_pos) {
         despawn_pool.add(entity, game_obj_lib)?;
         return Ok(MoveResult::NotMoved);
     }
 
-    let collided = check_collide(
+    let contact = get_contact(
         Some(entity),
         &new_pos,
         obj.collide_span,
@@ -128,7 +296,21 @@ pub fn move_missile(
         game_map,
         game_obj_lib,
     );
-    if collided {
+    if let Some(contact) = contact {
+        let hit_surface = match contact.entity {
+            Some(e) => game_obj_lib
+                .get(&e)
+                .is_some_and(|obj2| obj2.obj_type == GameObjType::Tile),
+            None => true,
+        };
+        if hit_surface
+            && let Some(bounce_ability) = bounce_ability
+            && bounce_ability.can_bounce()
+        {
+            bounce_ability.bounce(entity, contact.normal, transform, game_obj_lib)?;
+            return Ok(MoveResult::Bounced);
+        }
+
         on_death(
             entity,
             game_map,
@@ -233,8 +415,8 @@ fn get_collided_missiles(
     game_map: &GameMap,
     game_obj_lib: &GameObjLib,
     game_lib: &GameLib,
-) -> HashSet<Entity> {
-    let mut collided_missiles: HashSet<Entity> = HashSet::new();
+) -> Vec<Entity> {
+    let mut collided_missiles: Vec<Entity> = Vec::new();
     let total_span = collide_span + game_lib.game_config.max_collide_span;
     let region = game_map.get_region(
         pos.x - total_span,
@@ -250,38 +432,9 @@ fn get_collided_missiles(
             && obj.side != side
             && check_collide_obj(pos, collide_span, &obj.pos, obj.collide_span)
         {
-            collided_missiles.insert(entity);
+            collided_missiles.push(entity);
         }
     }
 
     collided_missiles
 }
-
-fn capture_goodies(
-    pos: &Vec2,
-    collide_span: f32,
-    game_map: &GameMap,
-    game_obj_lib: &mut GameObjLib,
-    game_lib: &GameLib,
-    despawn_pool: &mut DespawnPool,
-) -> Result<(), MyError> {
-    let total_span = collide_span + game_lib.game_config.max_collide_span;
-    let region = game_map.get_region(
-        pos.x - total_span,
-        pos.y - total_span,
-        pos.x + total_span,
-        pos.y + total_span,
-    );
-
-    for entity in game_map.map_iter(&region) {
-        if let Some(obj) = game_obj_lib.get(&entity)
-            && obj.obj_type == GameObjType::Goodie
-            && obj.state == GameObjState::Alive
-            && check_collide_obj(pos, collide_span, &obj.pos, obj.collide_span)
-        {
-            despawn_pool.add(entity, game_obj_lib)?;
-        }
-    }
-
-    Ok(())
-}
@@ -1,10 +1,13 @@
-use crate::config::{GameObjSide, OnDeathAction, SpawnMissileConfig};
+use crate::config::{
+    DamageType, GameObjSide, OnDeathAction, SpawnMissileConfig, StatusEffectConfig,
+};
 use crate::game::{
     GameObjState, GameObjType, Phaseout,
     components::{AiBotComponent, PlayoutComponent},
+    do_damage,
 };
 use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObj, NewObjQueue};
-use crate::misc::{MyError, check_collide_obj};
+use crate::misc::{MyError, check_collide_obj, game_rng};
 use crate::obj_missing_from_lib;
 use bevy::prelude::*;
 use rand::Rng;
@@ -32,6 +35,9 @@ pub fn on_death(
                     obj.side,
                     damage_config.damage_range,
                     damage_config.damage,
+                    damage_config.damage_type.unwrap_or(DamageType::Explosive),
+                    damage_config.knockback,
+                    damage_config.effects.as_deref().unwrap_or_default(),
                     game_map,
                     game_obj_lib,
                     game_lib,
@@ -62,6 +68,9 @@ fn on_do_damage(
     side: GameObjSide,
     damage_range: f32,
     damage: f32,
+    damage_type: DamageType,
+    knockback: Option<f32>,
+    effects: &[StatusEffectConfig],
     game_map: &GameMap,
     game_obj_lib: &mut GameObjLib,
     game_lib: &GameLib,
@@ -77,28 +86,31 @@ fn on_do_damage(
     );
 
     for entity in game_map.map_iter(&region) {
-        if let Some(obj) = game_obj_lib.get_mut(&entity)
-            && obj.state == GameObjState::Alive
+        if let Some(obj) = game_obj_lib.get(&entity)
+            && obj.is_damageable()
             && obj.side != side
-            && obj.obj_type == GameObjType::Bot
             && check_collide_obj(pos, damage_range, &obj.pos, obj.collide_span)
         {
-            if let Some(hp) = obj.hp {
-                let new_hp = (hp - damage).max(0.0);
-                obj.hp = Some(new_hp);
-                if new_hp == 0.0 {
-                    on_death(
-                        entity,
-                        game_map,
-                        game_obj_lib,
-                        game_lib,
-                        new_obj_queue,
-                        commands,
-                    )?;
-                }
-            } else {
-                error!("Bot's hp is none");
-                continue;
+            do_damage(
+                entity,
+                damage,
+                damage_type,
+                false,
+                effects,
+                game_map,
+                game_obj_lib,
+                game_lib,
+                new_obj_queue,
+                commands,
+            )?;
+
+            if let Some(knockback) = knockback
+                && let Some(obj) = game_obj_lib.get_mut(&entity)
+                && obj.obj_type == GameObjType::Bot
+                && obj.state == GameObjState::Alive
+            {
+                let push_dir = (obj.pos - *pos).try_normalize().unwrap_or(Vec2::ZERO);
+                obj.velocity += push_dir * knockback;
             }
         }
     }
@@ -117,6 +129,7 @@ fn on_play_frame(
         pos: pos.clone(),
         direction: Vec2::new(1.0, 0.0),
         speed: None,
+        owner: None,
     };
     new_obj_queue.push(new_obj);
     Ok(())
@@ -166,6 +179,7 @@ fn on_spawn_missile(
             pos: pos.clone(),
             direction: Vec2::new(angle.cos(), angle.sin()),
             speed: None,
+            owner: None,
         };
         new_obj_queue.push(new_obj);
         angle += delta_angle;
@@ -180,18 +194,19 @@ fn on_drop_goodie(
     game_lib: &GameLib,
     new_obj_queue: &mut NewObjQueue,
 ) -> Result<(), MyError> {
-    let mut rng = rand::rng();
+    let mut rng = game_rng();
 
     if rng.random_range(0.0..1.0) > prob {
         return Ok(());
     }
 
-    if let Some(config_index) = game_lib.goodies().choose(&mut rng) {
+    if let Some(config_index) = game_lib.goodies().choose(&mut *rng) {
         let new_obj = NewObj {
             config_index: *config_index,
             pos: pos.clone(),
             direction: Vec2::new(1.0, 0.0),
             speed: None,
+            owner: None,
         };
         new_obj_queue.push(new_obj);
     }
@@ -0,0 +1,172 @@
+use crate::game::GameObjType;
+use crate::game_utils::{GameLib, GameMap, GameObjLib};
+use crate::misc::{check_collide_bounds, check_collide_obj, get_collide_region};
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+type Cell = (usize, usize);
+
+// A* over the GameMap grid, returning the waypoints after start, ending at target.
+// Only cells where a bot of collide_span fits at the center are walked through.
+pub fn find_path(
+    start: &Vec2,
+    target: &Vec2,
+    collide_span: f32,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+) -> Option<Vec<Vec2>> {
+    let start_cell = (game_map.get_row(start.y), game_map.get_col(start.x));
+    let target_cell = (game_map.get_row(target.y), game_map.get_col(target.x));
+    let is_clear = |cell: Cell| is_clear(cell, collide_span, game_map, game_obj_lib, game_lib);
+
+    if !is_clear(target_cell) {
+        return None;
+    }
+
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<Cell, u32> = HashMap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+
+    open.push(Reverse((heuristic(start_cell, target_cell), 0, start_cell)));
+
+    costs.insert(start_cell, 0);
+
+    while let Some(Reverse((_, cost, cell))) = open.pop() {
+        if cell == target_cell {
+            return Some(build_waypoints(&came_from, target_cell, target, game_map));
+        }
+
+        if costs.get(&cell).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        for (next, step_cost) in neighbors(cell, game_map, &is_clear) {
+            let next_cost = cost + step_cost;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, cell);
+                open.push(Reverse((
+                    next_cost + heuristic(next, target_cell),
+                    next_cost,
+                    next,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+pub fn is_blocked(cell: Cell, game_map: &GameMap, game_obj_lib: &GameObjLib) -> bool {
+    game_map.map[cell.0][cell.1].iter().any(|entity| {
+        game_obj_lib
+            .get(entity)
+            .is_some_and(|obj| obj.obj_type == GameObjType::Tile && obj.is_collidable())
+    })
+}
+
+// Tiles are grown by the bot's size, since a bot wider than the gap between
+// two tiles can't pass even though the cells in between are empty
+fn is_clear(
+    cell: Cell,
+    collide_span: f32,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+) -> bool {
+    let center = game_map.get_cell_center(cell);
+    let cell_size = game_lib.game_config.cell_size;
+    if check_collide_bounds(
+        &center,
+        collide_span,
+        game_map.col_count() as f32 * cell_size,
+        game_map.row_count() as f32 * cell_size,
+    ) {
+        return false;
+    }
+
+    let region = get_collide_region(
+        &center,
+        collide_span,
+        game_lib.game_config.max_collide_span,
+        game_map,
+    );
+    !game_map.map_iter(&region).any(|entity| {
+        game_obj_lib.get(&entity).is_some_and(|obj| {
+            obj.obj_type == GameObjType::Tile
+                && obj.is_collidable()
+                && check_collide_obj(&center, collide_span, &obj.pos, obj.collide_span)
+        })
+    })
+}
+
+// Diagonal steps are only allowed when they don't cut a blocked corner
+fn neighbors(cell: Cell, game_map: &GameMap, is_clear: &impl Fn(Cell) -> bool) -> Vec<(Cell, u32)> {
+    let mut result = Vec::new();
+    let (row, col) = (cell.0 as i64, cell.1 as i64);
+    let row_count = game_map.row_count() as i64;
+    let col_count = game_map.col_count() as i64;
+    let free = |r: i64, c: i64| {
+        r >= 0 && r < row_count && c >= 0 && c < col_count && is_clear((r as usize, c as usize))
+    };
+
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if (dr == 0 && dc == 0) || !free(row + dr, col + dc) {
+                continue;
+            }
+
+            let step_cost = if dr != 0 && dc != 0 {
+                if !free(row + dr, col) || !free(row, col + dc) {
+                    continue;
+                }
+                DIAGONAL_COST
+            } else {
+                STRAIGHT_COST
+            };
+            result.push((((row + dr) as usize, (col + dc) as usize), step_cost));
+        }
+    }
+
+    result
+}
+
+fn heuristic(from: Cell, to: Cell) -> u32 {
+    let dr = from.0.abs_diff(to.0) as u32;
+    let dc = from.1.abs_diff(to.1) as u32;
+    STRAIGHT_COST * dr.max(dc) + (DIAGONAL_COST - STRAIGHT_COST) * dr.min(dc)
+}
+
+// Keeps only the cells where the route changes direction
+fn build_waypoints(
+    came_from: &HashMap<Cell, Cell>,
+    target_cell: Cell,
+    target: &Vec2,
+    game_map: &GameMap,
+) -> Vec<Vec2> {
+    let mut cells = vec![target_cell];
+    let mut cell = target_cell;
+    while let Some(prev) = came_from.get(&cell) {
+        cell = *prev;
+        cells.push(cell);
+    }
+    cells.reverse();
+
+    let mut waypoints = Vec::new();
+    for i in 1..cells.len().saturating_sub(1) {
+        let (prev, cur, next) = (cells[i - 1], cells[i], cells[i + 1]);
+        let step_in = (cur.0 as i64 - prev.0 as i64, cur.1 as i64 - prev.1 as i64);
+        let step_out = (next.0 as i64 - cur.0 as i64, next.1 as i64 - cur.1 as i64);
+        if step_in != step_out {
+            waypoints.push(game_map.get_cell_center(cur));
+        }
+    }
+
+    waypoints.push(*target);
+    waypoints
+}
@@ -0,0 +1,132 @@
+use crate::game::{
+    GameObjState, WeaponSwitch,
+    components::{PathComponent, WeaponComponent},
+    find_path, switch_weapon, try_shoot,
+};
+use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use crate::misc::{MyError, arr_to_vec2, get_rotation};
+use crate::obj_missing_from_lib;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub aim_target: Option<[f32; 2]>,
+    pub move_target: Option<[f32; 2]>,
+    pub queue_move: bool, // appends move_target to the waypoints instead of replacing them
+    pub move_direction: Option<[f32; 2]>, // zero to stop
+    pub mark_target: Option<[f32; 2]>,
+    pub fire: bool,
+    pub stop: bool,
+    pub reload: bool,
+    pub switch_weapon: Option<WeaponSwitch>,
+}
+
+pub fn apply_player_input(
+    entity: Entity,
+    input: &PlayerInput,
+    transform: &mut Transform,
+    weapon_comp: &mut WeaponComponent,
+    path_comp: &mut PathComponent,
+    world_info: &WorldInfo,
+    game_map: &GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    commands: &mut Commands,
+    time: &Time,
+) -> Result<(), MyError> {
+    let Some(obj) = game_obj_lib.get_mut(&entity) else {
+        return obj_missing_from_lib!();
+    };
+
+    if obj.state != GameObjState::Alive {
+        return Ok(());
+    }
+    let pos = obj.pos;
+    let collide_span = obj.collide_span;
+
+    weapon_comp.update_reload(time);
+    if input.reload {
+        weapon_comp.start_reload();
+    }
+
+    if let Some(aim_target) = input.aim_target.as_ref() {
+        if let Some(turret) = weapon_comp.turret.as_mut() {
+            turret.target = Some(arr_to_vec2(aim_target));
+        } else {
+            let direction = (arr_to_vec2(aim_target) - obj.pos).normalize();
+            obj.direction = direction;
+            transform.rotation = get_rotation(&direction);
+        }
+    }
+
+    if let Some(move_direction) = input.move_direction.as_ref() {
+        let config = game_lib
+            .get_game_obj_config(obj.config_index)
+            .bot_config()?;
+        let direction = arr_to_vec2(move_direction).normalize_or_zero();
+        path_comp.clear();
+        if direction == Vec2::ZERO {
+            obj.speed = Some(0.0);
+        } else {
+            obj.move_direction = Some(direction);
+            obj.speed = Some(config.speed);
+        }
+    }
+
+    if input.fire {
+        let speed = obj.speed.unwrap_or(0.0);
+        try_shoot(
+            entity,
+            speed,
+            weapon_comp,
+            world_info,
+            game_map,
+            game_obj_lib,
+            game_lib,
+            new_obj_queue,
+            commands,
+            time,
+        )?;
+    } else if input.stop {
+        obj.speed = Some(0.0);
+        path_comp.clear();
+    }
+
+    if let Some(move_target) = input.move_target.as_ref() {
+        let start = match path_comp.last() {
+            Some(last) if input.queue_move => last,
+            _ => pos,
+        };
+        match find_path(
+            &start,
+            &arr_to_vec2(move_target),
+            collide_span,
+            game_map,
+            game_obj_lib,
+            game_lib,
+        ) {
+            Some(waypoints) => {
+                if !input.queue_move {
+                    path_comp.clear();
+                }
+                path_comp.extend(waypoints);
+            }
+            None => warn!("No path to {:?}", move_target),
+        }
+    }
+
+    if let Some(weapon_switch) = input.switch_weapon {
+        switch_weapon(
+            entity,
+            weapon_switch,
+            weapon_comp,
+            game_obj_lib,
+            game_lib,
+            commands,
+        )?;
+    }
+
+    Ok(())
+}
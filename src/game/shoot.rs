@@ -1,5 +1,5 @@
-use crate::game::components::WeaponComponent;
-use crate::game_utils::{GameLib, GameObjLib, NewObj, NewObjQueue, WorldInfo};
+use crate::game::{components::WeaponComponent, fire_beam};
+use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObj, NewObjQueue, WorldInfo};
 use crate::misc::MyError;
 use crate::obj_missing_from_lib;
 use bevy::prelude::*;
@@ -9,42 +9,85 @@ pub fn try_shoot(
     base_speed: f32,
     weapon_comp: &mut WeaponComponent,
     world_info: &WorldInfo,
+    game_map: &GameMap,
     game_obj_lib: &mut GameObjLib,
     game_lib: &GameLib,
     new_obj_queue: &mut NewObjQueue,
+    commands: &mut Commands,
     time: &Time,
 ) -> Result<(), MyError> {
-    weapon_comp.fire_timer.tick(time.delta());
-    if !weapon_comp.fire_timer.is_finished() {
-        return Ok(());
-    }
-
     let Some(obj) = game_obj_lib.get(&entity).cloned() else {
         return obj_missing_from_lib!();
     };
     let base_velocity = base_speed * obj.direction;
+    let aim_direction = weapon_comp.aim_direction(obj.direction);
 
-    for i in 0..weapon_comp.fire_points.len() {
-        let missile_index = weapon_comp.missile_indices[i];
-        let missile_config = game_lib
-            .get_game_obj_config(missile_index)
-            .missile_config()?;
-        let pos = obj.pos + obj.direction.rotate(weapon_comp.fire_points[i]);
-        let relative_direction = obj.direction.rotate(weapon_comp.fire_directions[i]);
-        let velocity = relative_direction * missile_config.speed + base_velocity;
-        let direction = velocity.normalize();
-        let speed = Some(velocity.length());
-
-        if !world_info.check_pos_visible(&pos) {
+    for i in 0..weapon_comp.gun_states.len() {
+        weapon_comp.gun_states[i].fire_timer.tick(time.delta());
+        if !weapon_comp.gun_states[i].fire_timer.is_finished() || !weapon_comp.take_round() {
             continue;
         }
 
-        new_obj_queue.push(NewObj {
-            config_index: missile_index,
-            pos,
-            direction,
-            speed,
-        });
+        let gun_state = &mut weapon_comp.gun_states[i];
+        let missile_index = gun_state.missile_index;
+        let beam = gun_state.beam.clone();
+        let device_index = gun_state.device_index;
+
+        for (fire_point, fire_direction) in gun_state.fire() {
+            let pos = obj.pos + aim_direction.rotate(fire_point);
+            let relative_direction = aim_direction.rotate(fire_direction);
+
+            if let Some(beam) = beam.as_ref() {
+                fire_beam(
+                    entity,
+                    pos,
+                    relative_direction.normalize(),
+                    beam,
+                    world_info,
+                    game_map,
+                    game_obj_lib,
+                    game_lib,
+                    new_obj_queue,
+                    commands,
+                )?;
+                continue;
+            }
+
+            if let Some(device_index) = device_index {
+                if world_info.check_pos_simulated(&pos) {
+                    new_obj_queue.push(NewObj {
+                        config_index: device_index,
+                        pos,
+                        direction: relative_direction,
+                        speed: None,
+                        owner: Some(entity),
+                    });
+                }
+                continue;
+            }
+
+            let Some(missile_index) = missile_index else {
+                continue;
+            };
+            let missile_config = game_lib
+                .get_game_obj_config(missile_index)
+                .missile_config()?;
+            let velocity = relative_direction * missile_config.speed + base_velocity;
+            let direction = velocity.normalize();
+            let speed = Some(velocity.length());
+
+            if !world_info.check_pos_simulated(&pos) {
+                continue;
+            }
+
+            new_obj_queue.push(NewObj {
+                config_index: missile_index,
+                pos,
+                direction,
+                speed,
+                owner: Some(entity),
+            });
+        }
     }
 
     Ok(())
@@ -0,0 +1,68 @@
+use crate::game::{components::WeaponComponent, create_obj::create_weapon};
+use crate::game_utils::{GameLib, GameObjLib};
+use crate::misc::MyError;
+use crate::obj_missing_from_lib;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeaponSwitch {
+    Select(usize),
+    Next,
+    Prev,
+}
+
+pub fn switch_weapon(
+    entity: Entity,
+    weapon_switch: WeaponSwitch,
+    weapon_comp: &mut WeaponComponent,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+    commands: &mut Commands,
+) -> Result<(), MyError> {
+    let Some(obj) = game_obj_lib.get(&entity) else {
+        return obj_missing_from_lib!();
+    };
+    let config = game_lib
+        .get_game_obj_config(obj.config_index)
+        .bot_config()?;
+    let weapon_count = config.weapon_configs.len();
+
+    if weapon_count == 0 {
+        return Ok(());
+    }
+
+    let weapon_index = match weapon_switch {
+        WeaponSwitch::Select(index) => index,
+        WeaponSwitch::Next => (weapon_comp.weapon_index + 1) % weapon_count,
+        WeaponSwitch::Prev => (weapon_comp.weapon_index + weapon_count - 1) % weapon_count,
+    };
+
+    if weapon_index >= weapon_count || weapon_index == weapon_comp.weapon_index {
+        return Ok(());
+    }
+
+    for gun in weapon_comp.guns.drain(..) {
+        commands.entity(gun).despawn();
+    }
+
+    if let Some(magazine) = weapon_comp.magazine_mut() {
+        magazine.cancel_reload();
+    }
+
+    let magazines = std::mem::take(&mut weapon_comp.magazines);
+    let turret = weapon_comp.turret.take();
+    *weapon_comp = create_weapon(entity, weapon_index, config, magazines, game_lib, commands)?;
+
+    if let (Some(new_turret), Some(turret)) = (weapon_comp.turret.as_mut(), turret) {
+        new_turret.direction = turret.direction;
+        new_turret.target = turret.target;
+    }
+
+    debug!(
+        "Bot {} switched to weapon {}",
+        entity, config.weapon_configs[weapon_index].name
+    );
+
+    Ok(())
+}
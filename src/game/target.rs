@@ -0,0 +1,48 @@
+use crate::config::GameObjSide;
+use crate::game::{GameObjState, GameObjType};
+use crate::game_utils::{GameLib, GameMap, GameObjLib};
+use bevy::prelude::*;
+
+const MARK_SPAN: f32 = 50.0;
+
+// Finds the hostile bot nearest to the marked position
+pub fn find_marked_target(
+    pos: &Vec2,
+    side: GameObjSide,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+) -> Option<Entity> {
+    find_nearest_enemy(pos, side, MARK_SPAN, game_map, game_obj_lib, game_lib)
+}
+
+pub fn find_nearest_enemy(
+    pos: &Vec2,
+    side: GameObjSide,
+    span: f32,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+    game_lib: &GameLib,
+) -> Option<Entity> {
+    let total_span = span + game_lib.game_config.max_collide_span;
+    let region = game_map.get_region(
+        pos.x - total_span,
+        pos.y - total_span,
+        pos.x + total_span,
+        pos.y + total_span,
+    );
+
+    game_map
+        .map_iter(&region)
+        .filter_map(|e| {
+            let obj = game_obj_lib.get(&e)?;
+            let distance = obj.pos.distance(*pos);
+            (obj.state == GameObjState::Alive
+                && obj.side != side
+                && obj.obj_type == GameObjType::Bot
+                && distance <= span + obj.collide_span)
+                .then_some((distance, obj.spawn_id, e))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+        .map(|(_, _, e)| e)
+}
@@ -1,15 +1,21 @@
+pub mod action_state;
 pub mod despawn_pool;
+pub mod fog_map;
 pub mod game_info;
 pub mod game_lib;
 pub mod game_map;
 pub mod game_obj_lib;
+pub mod local_input;
 pub mod new_obj_queue;
 pub mod world_info;
 
+pub use action_state::ActionState;
 pub use despawn_pool::DespawnPool;
+pub use fog_map::{FogMap, FogState};
 pub use game_info::GameInfo;
 pub use game_lib::GameLib;
 pub use game_map::{GameMap, MapRegion, RectRegion};
 pub use game_obj_lib::GameObjLib;
+pub use local_input::LocalInput;
 pub use new_obj_queue::{NewObj, NewObjQueue};
 pub use world_info::WorldInfo;
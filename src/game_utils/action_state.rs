@@ -0,0 +1,64 @@
+use crate::config::InputAction;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const PRESS_THRESHOLD: f32 = 0.5;
+
+// Gameplay reads actions from here instead of raw keys, mouse buttons or gamepads,
+// so actions can also be injected directly with set and trigger
+#[derive(Resource, Default)]
+pub struct ActionState {
+    values: HashMap<InputAction, f32>,
+    prev_pressed: HashSet<InputAction>,
+    triggered: HashSet<InputAction>,
+}
+
+impl ActionState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Starts a new frame, remembering what was pressed for just_pressed
+    pub fn begin_frame(&mut self) {
+        self.prev_pressed = self
+            .values
+            .iter()
+            .filter(|(_, value)| **value >= PRESS_THRESHOLD)
+            .map(|(action, _)| *action)
+            .collect();
+        self.values.clear();
+        self.triggered.clear();
+    }
+
+    // The strongest of all bindings of an action wins
+    pub fn set(&mut self, action: InputAction, value: f32) {
+        let cur_value = self.values.entry(action).or_insert(0.0);
+        *cur_value = cur_value.max(value);
+    }
+
+    // For one-shot inputs like the mouse wheel which have no held state
+    pub fn trigger(&mut self, action: InputAction) {
+        self.set(action, 1.0);
+        self.triggered.insert(action);
+    }
+
+    #[inline]
+    pub fn value(&self, action: InputAction) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    #[inline]
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.value(action) >= PRESS_THRESHOLD
+    }
+
+    #[inline]
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.triggered.contains(&action)
+            || (self.pressed(action) && !self.prev_pressed.contains(&action))
+    }
+
+    pub fn axis(&self, negative: InputAction, positive: InputAction) -> f32 {
+        self.value(positive) - self.value(negative)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::game::MapPos;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FogState {
+    Unexplored,
+    Explored,
+    Visible,
+}
+
+#[derive(Resource)]
+pub struct FogMap {
+    row_count: usize,
+    col_count: usize,
+    cells: Vec<FogState>, // row-major from the bottom, like GameMap
+    center: Option<MapPos>,
+    visible: Vec<MapPos>,
+    newly_explored: Vec<MapPos>, // cells explored since the last take, for the minimap
+}
+
+impl FogMap {
+    pub fn new(row_count: usize, col_count: usize) -> Self {
+        Self {
+            row_count,
+            col_count,
+            cells: vec![FogState::Unexplored; row_count * col_count],
+            center: None,
+            visible: vec![],
+            newly_explored: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn state(&self, map_pos: &MapPos) -> FogState {
+        self.cells[map_pos.row * self.col_count + map_pos.col]
+    }
+
+    #[inline]
+    pub fn is_visible(&self, map_pos: &MapPos) -> bool {
+        self.state(map_pos) == FogState::Visible
+    }
+
+    #[inline]
+    pub fn take_newly_explored(&mut self) -> Vec<MapPos> {
+        std::mem::take(&mut self.newly_explored)
+    }
+
+    // Recomputes the visible cells around the center when it has moved to
+    // another cell, and returns every cell whose state changed
+    pub fn update(
+        &mut self,
+        center: MapPos,
+        radius: usize,
+        is_blocked: impl Fn(&MapPos) -> bool,
+    ) -> Vec<MapPos> {
+        if self.center == Some(center) {
+            return vec![];
+        }
+        self.center = Some(center);
+
+        let mut changes = vec![];
+        for map_pos in std::mem::take(&mut self.visible) {
+            self.set_state(&map_pos, FogState::Explored);
+            changes.push(map_pos);
+        }
+
+        let start_row = center.row.saturating_sub(radius);
+        let end_row = (center.row + radius).min(self.row_count - 1);
+        let start_col = center.col.saturating_sub(radius);
+        let end_col = (center.col + radius).min(self.col_count - 1);
+        let radius_squared = (radius * radius) as i64;
+
+        for row in start_row..=end_row {
+            for col in start_col..=end_col {
+                let map_pos = MapPos { row, col };
+                let d_row = row as i64 - center.row as i64;
+                let d_col = col as i64 - center.col as i64;
+                if d_row * d_row + d_col * d_col > radius_squared
+                    || !has_line_of_sight(&center, &map_pos, &is_blocked)
+                {
+                    continue;
+                }
+
+                if self.state(&map_pos) == FogState::Unexplored {
+                    self.newly_explored.push(map_pos);
+                }
+                self.set_state(&map_pos, FogState::Visible);
+                self.visible.push(map_pos);
+                changes.push(map_pos);
+            }
+        }
+
+        changes
+    }
+
+    #[inline]
+    fn set_state(&mut self, map_pos: &MapPos, state: FogState) {
+        self.cells[map_pos.row * self.col_count + map_pos.col] = state;
+    }
+}
+
+// Walks the cells between the two ends; the target itself may be blocked so
+// that walls facing the viewer are revealed
+fn has_line_of_sight(from: &MapPos, to: &MapPos, is_blocked: &impl Fn(&MapPos) -> bool) -> bool {
+    let d_row = to.row as f32 - from.row as f32;
+    let d_col = to.col as f32 - from.col as f32;
+    let steps = d_row.abs().max(d_col.abs()) as usize;
+
+    for step in 1..steps {
+        let t = step as f32 / steps as f32;
+        let map_pos = MapPos {
+            row: (from.row as f32 + d_row * t).round() as usize,
+            col: (from.col as f32 + d_col * t).round() as usize,
+        };
+        if is_blocked(&map_pos) {
+            return false;
+        }
+    }
+
+    true
+}
@@ -1,17 +1,28 @@
 use crate::misc::MyError;
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 #[derive(Resource, Debug)]
 pub struct GameInfo {
     ai_bot_count: usize,
+    waves_remaining: usize, // waves of all spawners not fully spawned yet
     player: Option<Entity>,
+    peer: Option<Entity>,
+    local_player_slot: usize,
+    player_bot_count: usize,
+    marked_targets: HashMap<usize, Entity>, // keyed by player slot
 }
 
 impl GameInfo {
-    pub fn new() -> Self {
+    pub fn new(local_player_slot: usize) -> Self {
         Self {
             ai_bot_count: 0,
+            waves_remaining: 0,
             player: None,
+            peer: None,
+            local_player_slot,
+            player_bot_count: 0,
+            marked_targets: HashMap::new(),
         }
     }
 
@@ -36,23 +47,93 @@ impl GameInfo {
         }
     }
 
+    #[inline]
+    pub fn waves_remaining(&self) -> usize {
+        self.waves_remaining
+    }
+
+    #[inline]
+    pub fn add_waves(&mut self, count: usize) {
+        self.waves_remaining += count;
+    }
+
+    pub fn dec_waves_remaining(&mut self) -> Result<(), MyError> {
+        if self.waves_remaining >= 1 {
+            self.waves_remaining -= 1;
+            Ok(())
+        } else {
+            let msg = "Try to decrease waves_remaining while waves_remaining is zero".to_string();
+            error!(msg);
+            Err(MyError::Other(msg))
+        }
+    }
+
     #[inline]
     pub fn get_player(&self) -> Option<Entity> {
         self.player
     }
 
     #[inline]
-    pub fn set_player(&mut self, entity: Entity) {
-        self.player = Some(entity);
+    pub fn get_peer(&self) -> Option<Entity> {
+        self.peer
+    }
+
+    #[inline]
+    pub fn local_player_slot(&self) -> usize {
+        self.local_player_slot
     }
 
+    // Player bots are assigned in map order: the one at local_player_slot is
+    // controlled by this instance and the other one by the network peer.
     #[inline]
-    pub fn clear_player(&mut self) {
-        self.player = None;
+    pub fn is_next_player_local(&self) -> bool {
+        self.player_bot_count == self.local_player_slot
+    }
+
+    pub fn add_player(&mut self, entity: Entity) {
+        if self.is_next_player_local() {
+            self.player = Some(entity);
+        } else {
+            self.peer = Some(entity);
+        }
+        self.player_bot_count += 1;
+    }
+
+    // There are two player slots, so the peer has the one that isn't local
+    pub fn player_slot(&self, entity: &Entity) -> Option<usize> {
+        if self.player == Some(*entity) {
+            Some(self.local_player_slot)
+        } else if self.peer == Some(*entity) {
+            Some(1 - self.local_player_slot)
+        } else {
+            None
+        }
+    }
+
+    pub fn remove_player(&mut self, entity: Entity) {
+        if self.player == Some(entity) {
+            self.player = None;
+        } else if self.peer == Some(entity) {
+            self.peer = None;
+        }
+    }
+
+    #[inline]
+    pub fn get_marked_target(&self, player_slot: usize) -> Option<Entity> {
+        self.marked_targets.get(&player_slot).cloned()
+    }
+
+    pub fn set_marked_target(&mut self, player_slot: usize, target: Option<Entity>) {
+        match target {
+            Some(target) => self.marked_targets.insert(player_slot, target),
+            None => self.marked_targets.remove(&player_slot),
+        };
     }
 
+    // All waves cleared when no spawner has bots left to emit and none are alive
     #[inline]
     pub fn is_game_over(&self) -> bool {
-        self.ai_bot_count == 0 || self.player.is_none()
+        (self.ai_bot_count == 0 && self.waves_remaining == 0)
+            || (self.player.is_none() && self.peer.is_none())
     }
 }
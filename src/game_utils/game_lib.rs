@@ -1,4 +1,7 @@
-use crate::config::{AiConfig, GameConfig, GameObjConfig, GunConfig, NamedGameObjConfig};
+use crate::config::{
+    AiConfig, GameConfig, GameObjConfig, GunConfig, InputBindingConfig, MissileFeature,
+    NamedGameObjConfig,
+};
 use crate::misc::{my_error::*, utils::*};
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -13,6 +16,7 @@ pub struct GameLib {
     gun_configs: HashMap<String, GunConfig>,
     texture_atlas_layouts: HashMap<String, Handle<TextureAtlasLayout>>,
     ai_configs: HashMap<String, AiConfig>,
+    input_configs: Vec<InputBindingConfig>,
     goodies: Vec<usize>,
 }
 
@@ -31,13 +35,17 @@ impl GameLib {
             gun_configs: HashMap::new(),
             texture_atlas_layouts: HashMap::new(),
             ai_configs: HashMap::new(),
+            input_configs: Vec::new(),
             goodies: Vec::new(),
         };
 
         game_lib.load_images(asset_server)?;
         game_lib.load_game_obj_configs(layouts)?;
+        game_lib.check_split_missiles()?;
+        game_lib.check_spawners()?;
         game_lib.load_gun_configs()?;
         game_lib.load_ai_configs()?;
+        game_lib.load_input_configs()?;
 
         info!("GameLib initialized");
 
@@ -117,6 +125,11 @@ impl GameLib {
         &self.goodies
     }
 
+    #[inline]
+    pub fn input_configs(&self) -> &Vec<InputBindingConfig> {
+        &self.input_configs
+    }
+
     fn load_images(&mut self, asset_server: &AssetServer) -> Result<(), MyError> {
         let assets_dir = PathBuf::from("assets");
         let image_dir = self.game_config.image_dir();
@@ -174,6 +187,50 @@ impl GameLib {
         Ok(())
     }
 
+    // Split missiles refer to their children by name, so a typo would only
+    // show up when a missile splits in the middle of a game
+    fn check_split_missiles(&self) -> Result<(), MyError> {
+        for named_config in self.game_obj_configs.iter() {
+            let GameObjConfig::Missile(cfg) = &named_config.config else {
+                continue;
+            };
+            for feature in cfg.features.iter() {
+                if let MissileFeature::Split(split_config) = feature {
+                    if split_config.delay.is_none() && split_config.trigger_range.is_none() {
+                        let msg = format!(
+                            "Split of missile {} has neither delay nor trigger_range and would never split",
+                            named_config.name
+                        );
+                        error!(msg);
+                        return Err(MyError::Other(msg));
+                    }
+                    let index = self.get_game_obj_config_index(&split_config.missile)?;
+                    self.get_game_obj_config(index).missile_config()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Spawners also refer to their bots by name, and an unknown one would hold
+    // its wave back forever
+    fn check_spawners(&self) -> Result<(), MyError> {
+        for named_config in self.game_obj_configs.iter() {
+            let GameObjConfig::Spawner(cfg) = &named_config.config else {
+                continue;
+            };
+            for wave in cfg.waves.iter() {
+                for spawn_bot in wave.bots.iter() {
+                    let index = self.get_game_obj_config_index(&spawn_bot.bot)?;
+                    self.get_game_obj_config(index).bot_config()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn load_gun_configs(&mut self) -> Result<(), MyError> {
         self.gun_configs = read_json(self.game_config.gun_config_file())?;
         info!("gun_configs loaded successfully");
@@ -186,6 +243,12 @@ impl GameLib {
         Ok(())
     }
 
+    fn load_input_configs(&mut self) -> Result<(), MyError> {
+        self.input_configs = read_json(self.game_config.input_config_file())?;
+        info!("input_configs loaded successfully");
+        Ok(())
+    }
+
     fn create_tex_atlas_layout(
         size: &[f32; 2],
         frame_count: usize,
@@ -1,11 +1,13 @@
 use crate::game::MapPos;
 use bevy::prelude::*;
-use std::collections::{HashSet, hash_set::Iter};
+use std::slice::Iter;
 
+// Cells keep their entities in the order they arrived, so that every lockstep
+// instance walks them the same way
 #[derive(Resource)]
 pub struct GameMap {
     cell_size: f32,
-    pub map: Vec<Vec<HashSet<Entity>>>,
+    pub map: Vec<Vec<Vec<Entity>>>,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
@@ -16,7 +18,7 @@ pub struct MapRegion {
     pub end_col: usize,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct RectRegion {
     pub left: f32,
     pub bottom: f32,
@@ -38,17 +40,20 @@ impl GameMap {
     pub fn new(row_count: usize, col_count: usize, cell_size: f32) -> Self {
         Self {
             cell_size,
-            map: vec![vec![HashSet::new(); col_count]; row_count],
+            map: vec![vec![Vec::new(); col_count]; row_count],
         }
     }
 
     pub fn add(&mut self, map_pos: &MapPos, entity: Entity) {
-        self.map[map_pos.row][map_pos.col].insert(entity);
+        let cell = &mut self.map[map_pos.row][map_pos.col];
+        if !cell.contains(&entity) {
+            cell.push(entity);
+        }
     }
 
     pub fn relocate(&mut self, entity: Entity, old_pos: &MapPos, new_pos: &MapPos) {
-        self.map[old_pos.row][old_pos.col].remove(&entity);
-        self.map[new_pos.row][new_pos.col].insert(entity);
+        Self::remove_from_cell(&mut self.map[old_pos.row][old_pos.col], &entity);
+        self.add(new_pos, entity);
     }
 
     #[inline]
@@ -63,7 +68,7 @@ impl GameMap {
 
     #[inline]
     pub fn remove(&mut self, entity: &Entity, map_pos: &MapPos) {
-        if !self.map[map_pos.row][map_pos.col].remove(entity) {
+        if !Self::remove_from_cell(&mut self.map[map_pos.row][map_pos.col], entity) {
             error!(
                 "Cannot remove entity {:?} from GameMap at position {:?}",
                 entity, map_pos
@@ -79,6 +84,14 @@ impl GameMap {
         }
     }
 
+    #[inline]
+    pub fn get_cell_center(&self, (row, col): (usize, usize)) -> Vec2 {
+        Vec2::new(
+            (col as f32 + 0.5) * self.cell_size,
+            (row as f32 + 0.5) * self.cell_size,
+        )
+    }
+
     #[inline]
     pub fn get_row(&self, y: f32) -> usize {
         let i = (y / self.cell_size).floor() as i32;
@@ -109,6 +122,17 @@ impl GameMap {
     pub fn map_iter<'a>(&'a self, region: &MapRegion) -> MapIterator<'a> {
         MapIterator::new(&self, region)
     }
+
+    // Keeps the order of the remaining entities
+    fn remove_from_cell(cell: &mut Vec<Entity>, entity: &Entity) -> bool {
+        match cell.iter().position(|e| e == entity) {
+            Some(index) => {
+                cell.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl<'a> MapIterator<'a> {
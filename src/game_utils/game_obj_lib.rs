@@ -1,32 +1,107 @@
-use crate::game::GameObj;
+use crate::game::{GameObj, GameObjType, MapPos};
 use bevy::prelude::*;
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 #[derive(Resource)]
-pub struct GameObjLib(HashMap<Entity, GameObj>);
+pub struct GameObjLib {
+    objs: HashMap<Entity, GameObj>,
+    map_changes: Vec<MapPos>, // cells whose tiles or goodies were added or removed
+    next_spawn_id: u64,
+}
 
 impl GameObjLib {
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self {
+            objs: HashMap::new(),
+            map_changes: vec![],
+            next_spawn_id: 0,
+        }
     }
 
     #[inline]
     pub fn get(&self, entity: &Entity) -> Option<&GameObj> {
-        self.0.get(entity)
+        self.objs.get(entity)
     }
 
     #[inline]
     pub fn get_mut(&mut self, entity: &Entity) -> Option<&mut GameObj> {
-        self.0.get_mut(entity)
+        self.objs.get_mut(entity)
     }
 
-    #[inline]
     pub fn remove(&mut self, entity: &Entity) {
-        self.0.remove(entity);
+        if let Some(obj) = self.objs.remove(entity) {
+            self.track_map_change(&obj);
+        }
     }
 
-    #[inline]
     pub fn insert(&mut self, entity: Entity, obj: GameObj) {
-        self.0.insert(entity, obj);
+        self.track_map_change(&obj);
+        self.objs.insert(entity, obj);
+    }
+
+    #[inline]
+    pub fn next_spawn_id(&mut self) -> u64 {
+        self.next_spawn_id += 1;
+        self.next_spawn_id
+    }
+
+    // Queries visit entities by archetype, and view-only markers such as InView
+    // put them in different archetypes on each lockstep instance. The simulation
+    // goes by spawn order instead; entities missing from the lib are dropped.
+    pub fn in_spawn_order(&self, entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut entities: Vec<(u64, Entity)> = entities
+            .filter_map(|entity| self.objs.get(&entity).map(|obj| (obj.spawn_id, entity)))
+            .collect();
+        entities.sort_unstable_by_key(|(spawn_id, _)| *spawn_id);
+        entities.into_iter().map(|(_, entity)| entity).collect()
+    }
+
+    // Tiles and goodies never move, so the cells they are added to or removed
+    // from are all a view of the static map has to redraw
+    #[inline]
+    pub fn take_map_changes(&mut self) -> Vec<MapPos> {
+        std::mem::take(&mut self.map_changes)
+    }
+
+    fn track_map_change(&mut self, obj: &GameObj) {
+        if obj.obj_type == GameObjType::Tile || obj.obj_type == GameObjType::Goodie {
+            self.map_changes.push(obj.map_pos);
+        }
+    }
+
+    // Entity ids differ between instances, so the hash only covers the
+    // simulated state of each object and is independent of iteration order.
+    // State kept in components is added to each object by hash_components.
+    pub fn state_hash(&self, hash_components: impl Fn(&Entity, &mut DefaultHasher)) -> u64 {
+        let mut obj_hashes: Vec<u64> = self
+            .objs
+            .iter()
+            .map(|(entity, obj)| {
+                let mut hasher = DefaultHasher::new();
+                obj.config_index.hash(&mut hasher);
+                obj.pos.x.to_bits().hash(&mut hasher);
+                obj.pos.y.to_bits().hash(&mut hasher);
+                obj.direction.x.to_bits().hash(&mut hasher);
+                obj.direction.y.to_bits().hash(&mut hasher);
+                obj.speed.map(f32::to_bits).hash(&mut hasher);
+                obj.move_velocity.x.to_bits().hash(&mut hasher);
+                obj.move_velocity.y.to_bits().hash(&mut hasher);
+                obj.velocity.x.to_bits().hash(&mut hasher);
+                obj.velocity.y.to_bits().hash(&mut hasher);
+                obj.hp.map(f32::to_bits).hash(&mut hasher);
+                (obj.state as u8).hash(&mut hasher);
+                if let Some(shield) = obj.shield.as_ref() {
+                    shield.hash_state(&mut hasher);
+                }
+                hash_components(entity, &mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        obj_hashes.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        obj_hashes.hash(&mut hasher);
+        hasher.finish()
     }
 }
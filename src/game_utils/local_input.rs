@@ -0,0 +1,15 @@
+use crate::game::PlayerInput;
+use bevy::prelude::*;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct LocalInput(PlayerInput);
+
+impl LocalInput {
+    pub fn new() -> Self {
+        Self(PlayerInput::default())
+    }
+
+    pub fn take(&mut self) -> PlayerInput {
+        std::mem::take(&mut self.0)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::{GameObjConfig, GameObjSide};
+use crate::game_utils::GameLib;
 use bevy::prelude::*;
 
 pub struct NewObj {
@@ -5,6 +7,7 @@ pub struct NewObj {
     pub pos: Vec2,
     pub direction: Vec2,
     pub speed: Option<f32>,
+    pub owner: Option<Entity>, // the bot that fired or deployed it
 }
 
 #[derive(Resource, Deref, DerefMut)]
@@ -14,4 +17,17 @@ impl NewObjQueue {
     pub fn new() -> Self {
         Self(Vec::new())
     }
+
+    // AI bots are only counted by GameInfo once they are added, so the ones
+    // still waiting here have to be counted on top
+    pub fn ai_bot_count(&self, game_lib: &GameLib) -> usize {
+        self.iter()
+            .filter(|new_obj| {
+                matches!(
+                    &game_lib.get_game_obj_config(new_obj.config_index).config,
+                    GameObjConfig::Bot(cfg) if cfg.side == GameObjSide::Ai
+                )
+            })
+            .count()
+    }
 }
@@ -6,10 +6,11 @@ pub struct WorldInfo {
     world_width: f32,
     world_height: f32,
     world_region: RectRegion,
-    min_origin: Vec2,
-    max_origin: Vec2,
+    window_size: Vec2,
+    visible_ext_size: f32,
+    simulate_whole_world: bool,
+    zoom: f32,
     origin: Vec2,
-    visible_span: Vec2,
     visible_region: RectRegion,
 }
 
@@ -20,22 +21,18 @@ impl WorldInfo {
         window_width: f32,
         window_height: f32,
         visible_ext_size: f32,
+        simulate_whole_world: bool,
         origin: &Vec2,
     ) -> Self {
         let mut world_info = Self {
             world_width,
             world_height,
             world_region: RectRegion::new(0.0, 0.0, world_width, world_height),
-            min_origin: Vec2::new(window_width / 2.0, window_height / 2.0),
-            max_origin: Vec2::new(
-                world_width - window_width / 2.0,
-                world_height - window_height / 2.0,
-            ),
+            window_size: Vec2::new(window_width, window_height),
+            visible_ext_size,
+            simulate_whole_world,
+            zoom: 1.0,
             origin: Vec2::default(),
-            visible_span: Vec2::new(
-                window_width / 2.0 + visible_ext_size,
-                window_height / 2.0 + visible_ext_size,
-            ),
             visible_region: RectRegion::default(),
         };
 
@@ -59,6 +56,11 @@ impl WorldInfo {
         self.origin.clone()
     }
 
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
     #[inline]
     pub fn get_screen_pos(&self, pos: &Vec2) -> Vec2 {
         pos - self.origin
@@ -70,15 +72,35 @@ impl WorldInfo {
     }
 
     pub fn set_origin(&mut self, origin: &Vec2) {
-        self.origin.x = origin.x.clamp(self.min_origin.x, self.max_origin.x);
-        self.origin.y = origin.y.clamp(self.min_origin.y, self.max_origin.y);
-        self.visible_region.left =
-            (self.origin.x - self.visible_span.x).max(self.world_region.left);
-        self.visible_region.bottom =
-            (self.origin.y - self.visible_span.y).max(self.world_region.bottom);
-        self.visible_region.right =
-            (self.origin.x + self.visible_span.x).min(self.world_region.right);
-        self.visible_region.top = (self.origin.y + self.visible_span.y).min(self.world_region.top);
+        // Half of the window in world units; when the view is larger than the
+        // world along an axis, the world is centered along that axis
+        let half_view = self.window_size / 2.0 / self.zoom;
+        let world_size = Vec2::new(self.world_width, self.world_height);
+        for i in 0..2 {
+            self.origin[i] = if half_view[i] * 2.0 >= world_size[i] {
+                world_size[i] / 2.0
+            } else {
+                origin[i].clamp(half_view[i], world_size[i] - half_view[i])
+            };
+        }
+
+        let visible_span = half_view + Vec2::splat(self.visible_ext_size);
+        self.visible_region.left = (self.origin.x - visible_span.x).max(self.world_region.left);
+        self.visible_region.bottom = (self.origin.y - visible_span.y).max(self.world_region.bottom);
+        self.visible_region.right = (self.origin.x + visible_span.x).min(self.world_region.right);
+        self.visible_region.top = (self.origin.y + visible_span.y).min(self.world_region.top);
+    }
+
+    // Takes effect on the visible region with the next set_origin
+    #[inline]
+    pub fn set_window_size(&mut self, window_width: f32, window_height: f32) {
+        self.window_size = Vec2::new(window_width, window_height);
+    }
+
+    // Takes effect on the visible region with the next set_origin
+    #[inline]
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom;
     }
 
     #[inline]
@@ -86,6 +108,17 @@ impl WorldInfo {
         self.visible_region.covers(pos)
     }
 
+    // Objects outside of the simulated region are culled. In lockstep both
+    // instances have to simulate the same objects, so it can't depend on the view.
+    #[inline]
+    pub fn check_pos_simulated(&self, pos: &Vec2) -> bool {
+        if self.simulate_whole_world {
+            self.world_region.covers(pos)
+        } else {
+            self.visible_region.covers(pos)
+        }
+    }
+
     #[inline]
     pub fn contains(&self, pos: &Vec2) -> bool {
         self.world_region.covers(pos)
@@ -3,6 +3,7 @@ mod config;
 mod game;
 mod game_utils;
 mod misc;
+mod net;
 mod systems;
 
 use crate::misc::{AppMode, Args, setup_log};
@@ -1,11 +1,14 @@
 pub mod collide;
+pub mod game_rng;
 pub mod my_error;
 pub mod states;
 pub mod utils;
 
 pub use collide::{
     check_collide, check_collide_bounds, check_collide_obj, check_collide_objs, get_collide_region,
+    get_contact,
 };
+pub use game_rng::{game_rng, seed_game_rng};
 pub use my_error::MyError;
 pub use states::{AppState, GameState};
 pub use utils::{
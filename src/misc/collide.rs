@@ -1,6 +1,12 @@
 use crate::game_utils::{GameMap, GameObjLib, MapRegion, WorldInfo};
 use bevy::prelude::*;
 
+#[derive(Debug, Clone, Copy)]
+pub struct Contact {
+    pub entity: Option<Entity>, // None if the world bounds were hit
+    pub normal: Vec2,
+}
+
 pub fn check_collide(
     entity: Option<Entity>,
     pos: &Vec2,
@@ -10,19 +16,43 @@ pub fn check_collide(
     game_map: &GameMap,
     game_obj_lib: &GameObjLib,
 ) -> bool {
-    check_collide_bounds(
-        pos,
-        collide_span,
-        world_info.world_width(),
-        world_info.world_height(),
-    ) || check_collide_objs(
+    get_contact(
         entity,
         pos,
         collide_span,
         max_collide_span,
+        world_info,
         game_map,
         game_obj_lib,
     )
+    .is_some()
+}
+
+pub fn get_contact(
+    entity: Option<Entity>,
+    pos: &Vec2,
+    collide_span: f32,
+    max_collide_span: f32,
+    world_info: &WorldInfo,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+) -> Option<Contact> {
+    get_bounds_contact(
+        pos,
+        collide_span,
+        world_info.world_width(),
+        world_info.world_height(),
+    )
+    .or_else(|| {
+        get_objs_contact(
+            entity,
+            pos,
+            collide_span,
+            max_collide_span,
+            game_map,
+            game_obj_lib,
+        )
+    })
 }
 
 #[inline]
@@ -51,6 +81,48 @@ pub fn check_collide_bounds(new_pos: &Vec2, collide_span: f32, width: f32, heigh
         || new_pos.y + collide_span > height
 }
 
+pub fn get_bounds_contact(
+    new_pos: &Vec2,
+    collide_span: f32,
+    width: f32,
+    height: f32,
+) -> Option<Contact> {
+    let normal = if new_pos.x - collide_span < 0.0 {
+        Vec2::X
+    } else if new_pos.x + collide_span > width {
+        Vec2::NEG_X
+    } else if new_pos.y - collide_span < 0.0 {
+        Vec2::Y
+    } else if new_pos.y + collide_span > height {
+        Vec2::NEG_Y
+    } else {
+        return None;
+    };
+
+    Some(Contact {
+        entity: None,
+        normal,
+    })
+}
+
+// Collide regions are squares, so the normal is along the axis with the
+// smallest overlap, pointing from obj2 toward obj1.
+#[inline]
+pub fn get_contact_normal(
+    pos1: &Vec2,
+    collide_span1: f32,
+    pos2: &Vec2,
+    collide_span2: f32,
+) -> Vec2 {
+    let total_span = collide_span1 + collide_span2;
+    let d = pos1 - pos2;
+    if total_span - d.x.abs() < total_span - d.y.abs() {
+        Vec2::new(d.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, d.y.signum())
+    }
+}
+
 pub fn check_collide_objs(
     entity: Option<Entity>,
     pos: &Vec2,
@@ -59,6 +131,25 @@ pub fn check_collide_objs(
     game_map: &GameMap,
     game_obj_lib: &GameObjLib,
 ) -> bool {
+    get_objs_contact(
+        entity,
+        pos,
+        collide_span,
+        max_collide_span,
+        game_map,
+        game_obj_lib,
+    )
+    .is_some()
+}
+
+pub fn get_objs_contact(
+    entity: Option<Entity>,
+    pos: &Vec2,
+    collide_span: f32,
+    max_collide_span: f32,
+    game_map: &GameMap,
+    game_obj_lib: &GameObjLib,
+) -> Option<Contact> {
     let collide_region = get_collide_region(pos, collide_span, max_collide_span, game_map);
 
     for e in game_map.map_iter(&collide_region) {
@@ -77,9 +168,12 @@ pub fn check_collide_objs(
 
         if check_collide_obj(&pos, collide_span, &obj2.pos, obj2.collide_span) {
             info!("{:?} collide with {:?}", pos, obj2);
-            return true;
+            return Some(Contact {
+                entity: Some(e),
+                normal: get_contact_normal(pos, collide_span, &obj2.pos, obj2.collide_span),
+            });
         }
     }
 
-    false
+    None
 }
@@ -0,0 +1,18 @@
+use once_cell::sync::Lazy;
+use rand::{SeedableRng, rngs::SmallRng};
+use std::sync::{Mutex, MutexGuard};
+
+static GAME_RNG: Lazy<Mutex<SmallRng>> = Lazy::new(|| Mutex::new(SmallRng::from_os_rng()));
+
+pub fn seed_game_rng(seed: u64) {
+    *game_rng() = SmallRng::seed_from_u64(seed);
+}
+
+// Every random decision made by the simulation must come from here so that
+// two instances seeded with the same value stay in sync.
+pub fn game_rng() -> MutexGuard<'static, SmallRng> {
+    match GAME_RNG.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
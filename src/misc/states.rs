@@ -13,6 +13,7 @@ pub enum GameState {
     #[default]
     Loading,
     Play,
+    Paused,
     GameOver,
     End,
 }
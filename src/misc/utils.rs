@@ -7,6 +7,7 @@ use serde_json;
 use std::{
     fs::File,
     io::BufReader,
+    net::SocketAddr,
     path::{Path, PathBuf},
 };
 use tracing_appender::non_blocking::WorkerGuard;
@@ -34,6 +35,21 @@ pub struct Args {
 
     #[arg(long)]
     pub map: Option<PathBuf>,
+
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[arg(long)]
+    pub net_bind: Option<SocketAddr>,
+
+    #[arg(long)]
+    pub net_peer: Option<SocketAddr>,
+
+    #[arg(long)]
+    pub net_host: bool,
+
+    #[arg(long, default_value_t = 2)]
+    pub net_input_delay: u64,
 }
 
 pub fn read_json<T, P>(path: P) -> Result<T, MyError>
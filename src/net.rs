@@ -0,0 +1,5 @@
+pub mod net_message;
+pub mod net_session;
+
+pub use net_message::NetMessage;
+pub use net_session::{NET_TICK_SECS, NetSession};
@@ -0,0 +1,15 @@
+use crate::game::PlayerInput;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetMessage {
+    Inputs {
+        ack_tick: u64,
+        start_tick: u64,
+        inputs: Vec<PlayerInput>,
+    },
+    StateHash {
+        tick: u64,
+        hash: u64,
+    },
+}
@@ -0,0 +1,312 @@
+use crate::game::PlayerInput;
+use crate::misc::MyError;
+use crate::net::NetMessage;
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+pub const NET_TICK_SECS: f64 = 1.0 / 60.0;
+const MAX_DATAGRAM_SIZE: usize = 65536;
+const HASH_HISTORY_TICKS: u64 = 600;
+const PEER_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Resource)]
+pub struct NetSession {
+    socket: UdpSocket,
+    peer: SocketAddr,
+    input_delay: u64,
+    tick: u64,
+    peer_tick: u64,
+    local_inputs: BTreeMap<u64, PlayerInput>,
+    remote_inputs: BTreeMap<u64, PlayerInput>,
+    local_hashes: HashMap<u64, u64>,
+    remote_hashes: HashMap<u64, u64>,
+    desync_tick: Option<u64>,
+    last_received: Option<Instant>, // None until the peer has been heard from
+}
+
+impl NetSession {
+    pub fn new(bind: SocketAddr, peer: SocketAddr, input_delay: u64) -> Result<Self, MyError> {
+        let socket = UdpSocket::bind(bind)?;
+        socket.set_nonblocking(true)?;
+
+        let mut session = Self {
+            socket,
+            peer,
+            input_delay,
+            tick: 0,
+            peer_tick: 0,
+            local_inputs: BTreeMap::new(),
+            remote_inputs: BTreeMap::new(),
+            local_hashes: HashMap::new(),
+            remote_hashes: HashMap::new(),
+            desync_tick: None,
+            last_received: None,
+        };
+
+        // Inputs are scheduled input_delay ticks ahead, so nobody can act
+        // during the first ticks and both sides agree they are empty.
+        for tick in 0..input_delay {
+            session.local_inputs.insert(tick, PlayerInput::default());
+            session.remote_inputs.insert(tick, PlayerInput::default());
+        }
+
+        info!("NetSession bound to {} with peer {}", bind, peer);
+
+        Ok(session)
+    }
+
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.local_inputs.contains_key(&self.tick) && self.remote_inputs.contains_key(&self.tick)
+    }
+
+    // Waiting for the peer to start is fine, but once it has been heard from,
+    // a silent peer is taken as disconnected
+    #[inline]
+    pub fn is_peer_timed_out(&self) -> bool {
+        self.last_received
+            .is_some_and(|instant| instant.elapsed() > PEER_TIMEOUT)
+    }
+
+    #[inline]
+    pub fn is_desynced(&self) -> bool {
+        self.desync_tick.is_some()
+    }
+
+    #[inline]
+    pub fn needs_local_input(&self) -> bool {
+        !self
+            .local_inputs
+            .contains_key(&(self.tick + self.input_delay))
+    }
+
+    pub fn schedule_local_input(&mut self, input: PlayerInput) {
+        self.local_inputs
+            .insert(self.tick + self.input_delay, input);
+    }
+
+    pub fn current_inputs(&self) -> Option<(PlayerInput, PlayerInput)> {
+        let local = self.local_inputs.get(&self.tick)?;
+        let remote = self.remote_inputs.get(&self.tick)?;
+        Some((*local, *remote))
+    }
+
+    // Resends every input the peer may still be missing, which covers lost
+    // datagrams without needing acknowledgements of individual packets.
+    pub fn send_inputs(&self) -> Result<(), MyError> {
+        let Some(start_tick) = self.local_inputs.keys().next().cloned() else {
+            return Ok(());
+        };
+        let inputs: Vec<PlayerInput> = self.local_inputs.values().cloned().collect();
+
+        self.send(&NetMessage::Inputs {
+            ack_tick: self.tick,
+            start_tick,
+            inputs,
+        })
+    }
+
+    pub fn poll(&mut self) -> Result<(), MyError> {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, addr)) => {
+                    if addr != self.peer {
+                        warn!("Ignored datagram from unknown address {}", addr);
+                        continue;
+                    }
+
+                    match serde_json::from_slice::<NetMessage>(&buf[..len]) {
+                        Ok(msg) => {
+                            self.last_received = Some(Instant::now());
+                            self.on_message(msg);
+                        }
+                        Err(err) => warn!("Failed to parse NetMessage: {}", err),
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(err)
+                    if err.kind() == ErrorKind::ConnectionReset
+                        || err.kind() == ErrorKind::ConnectionRefused =>
+                {
+                    continue; // Peer is not listening yet
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    pub fn finish_tick(&mut self, state_hash: u64) -> Result<(), MyError> {
+        let tick = self.tick;
+
+        self.local_hashes.insert(tick, state_hash);
+        self.send(&NetMessage::StateHash {
+            tick,
+            hash: state_hash,
+        })?;
+        self.compare_hashes(tick);
+
+        self.tick += 1;
+        self.remote_inputs.retain(|t, _| *t > tick);
+        self.prune_local_inputs();
+        self.local_hashes
+            .retain(|t, _| *t + HASH_HISTORY_TICKS > tick);
+
+        Ok(())
+    }
+
+    fn on_message(&mut self, msg: NetMessage) {
+        match msg {
+            NetMessage::Inputs {
+                ack_tick,
+                start_tick,
+                inputs,
+            } => {
+                for (i, input) in inputs.into_iter().enumerate() {
+                    let tick = start_tick + i as u64;
+                    if tick >= self.tick {
+                        self.remote_inputs.entry(tick).or_insert(input);
+                    }
+                }
+
+                self.peer_tick = self.peer_tick.max(ack_tick);
+                self.prune_local_inputs();
+            }
+            NetMessage::StateHash { tick, hash } => {
+                self.remote_hashes.insert(tick, hash);
+                self.compare_hashes(tick);
+            }
+        }
+    }
+
+    // Local inputs are kept until both sides have simulated their tick.
+    fn prune_local_inputs(&mut self) {
+        let min_tick = self.tick.min(self.peer_tick);
+        self.local_inputs.retain(|t, _| *t >= min_tick);
+    }
+
+    fn compare_hashes(&mut self, tick: u64) {
+        let (Some(local_hash), Some(remote_hash)) =
+            (self.local_hashes.get(&tick), self.remote_hashes.get(&tick))
+        else {
+            return;
+        };
+
+        if local_hash != remote_hash && self.desync_tick.is_none() {
+            error!(
+                "Desync detected at tick {}: local hash {:x}, remote hash {:x}",
+                tick, local_hash, remote_hash
+            );
+            self.desync_tick = Some(tick);
+        }
+
+        self.local_hashes.remove(&tick);
+        self.remote_hashes.remove(&tick);
+    }
+
+    fn send(&self, msg: &NetMessage) -> Result<(), MyError> {
+        let data = serde_json::to_vec(msg)?;
+
+        match self.socket.send_to(&data, self.peer) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loopback_pair(port: u16) -> (NetSession, NetSession) {
+        let addr_a: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+        let addr_b: SocketAddr = format!("127.0.0.1:{}", port + 1).parse().unwrap();
+        let session_a = NetSession::new(addr_a, addr_b, 2).unwrap();
+        let session_b = NetSession::new(addr_b, addr_a, 2).unwrap();
+        (session_a, session_b)
+    }
+
+    // Datagrams on loopback arrive almost at once, but not within the same call
+    fn poll_until(sessions: [&mut NetSession; 2], done: impl Fn(&NetSession, &NetSession) -> bool) {
+        let [session_a, session_b] = sessions;
+        for _ in 0..1000 {
+            session_a.poll().unwrap();
+            session_b.poll().unwrap();
+            if done(session_a, session_b) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("Sessions did not converge");
+    }
+
+    fn step(session: &mut NetSession, input: PlayerInput) {
+        if session.needs_local_input() {
+            session.schedule_local_input(input);
+        }
+        session.send_inputs().unwrap();
+    }
+
+    #[test]
+    fn exchanges_inputs_and_matching_hashes() {
+        let (mut session_a, mut session_b) = loopback_pair(47310);
+        let input_a = PlayerInput {
+            fire: true,
+            ..default()
+        };
+        let input_b = PlayerInput {
+            reload: true,
+            ..default()
+        };
+
+        for tick in 0..10 {
+            step(&mut session_a, input_a);
+            step(&mut session_b, input_b);
+            poll_until([&mut session_a, &mut session_b], |a, b| {
+                a.is_ready() && b.is_ready()
+            });
+
+            let (local_a, remote_a) = session_a.current_inputs().unwrap();
+            let (local_b, remote_b) = session_b.current_inputs().unwrap();
+            assert_eq!(local_a, remote_b);
+            assert_eq!(local_b, remote_a);
+            if tick >= 2 {
+                assert_eq!(local_a, input_a);
+                assert_eq!(local_b, input_b);
+            }
+
+            session_a.finish_tick(tick).unwrap();
+            session_b.finish_tick(tick).unwrap();
+        }
+
+        poll_until([&mut session_a, &mut session_b], |a, b| {
+            a.remote_hashes.is_empty() && b.remote_hashes.is_empty()
+        });
+        assert!(!session_a.is_desynced());
+        assert!(!session_b.is_desynced());
+    }
+
+    #[test]
+    fn detects_hash_mismatch() {
+        let (mut session_a, mut session_b) = loopback_pair(47320);
+
+        step(&mut session_a, PlayerInput::default());
+        step(&mut session_b, PlayerInput::default());
+        poll_until([&mut session_a, &mut session_b], |a, b| {
+            a.is_ready() && b.is_ready()
+        });
+
+        session_a.finish_tick(1).unwrap();
+        session_b.finish_tick(2).unwrap();
+        poll_until([&mut session_a, &mut session_b], |a, b| {
+            a.is_desynced() && b.is_desynced()
+        });
+        assert_eq!(session_a.desync_tick, Some(0));
+        assert_eq!(session_b.desync_tick, Some(0));
+    }
+}
@@ -14,32 +14,66 @@ mod game_play {
     pub mod add_new_objs;
     pub mod check_game;
     pub mod cleanup;
+    pub mod combat_feedback;
+    pub mod draw_paths;
     pub mod gameover;
+    pub mod hud;
+    pub mod minimap;
+    pub mod pause;
+    pub mod process_actions;
     pub mod process_cursor;
-    pub mod process_key;
-    pub mod process_mouse_button;
+    pub mod resize_window;
     pub mod setup;
+    pub mod sync_net;
+    pub mod update_action_state;
     pub mod update_ai;
     pub mod update_ai_bots;
+    pub mod update_beams;
+    pub mod update_camera;
+    pub mod update_devices;
+    pub mod update_fog;
+    pub mod update_knockback;
     pub mod update_missiles;
     pub mod update_origin;
     pub mod update_player;
+    pub mod update_player_input;
     pub mod update_playout;
+    pub mod update_shields;
+    pub mod update_spawners;
+    pub mod update_status_effects;
+    pub mod update_turrets;
 
     pub use add_new_objs::add_new_objs;
     pub use check_game::check_game;
     pub use cleanup::cleanup;
+    pub use combat_feedback::{spawn_combat_feedback, update_combat_feedback};
+    pub use draw_paths::draw_paths;
     pub use gameover::{gameover, wait_gameover};
+    pub use hud::{setup_hud, update_hud};
+    pub use minimap::{setup_minimap, update_minimap};
+    pub use pause::{pause, toggle_pause};
+    pub use process_actions::process_actions;
     pub use process_cursor::process_cursor;
-    pub use process_key::process_key;
-    pub use process_mouse_button::process_mouse_button;
+    pub use resize_window::resize_window;
     pub use setup::setup_game;
+    pub use sync_net::{finish_net_tick, net_tick_ready, sync_net};
+    pub use update_action_state::update_action_state;
     pub use update_ai::update_ai;
     pub use update_ai_bots::update_ai_bots;
+    pub use update_beams::update_beams;
+    pub use update_camera::{reset_camera, update_camera};
+    pub use update_devices::update_devices;
+    pub use update_fog::{setup_fog, update_fog};
+    pub use update_knockback::update_knockback;
     pub use update_missiles::update_missiles;
     pub use update_origin::update_origin;
     pub use update_player::update_player;
+    pub use update_player_input::update_player_input;
     pub use update_playout::update_playout;
+    pub use update_shields::update_shields;
+    pub use update_spawners::update_spawners;
+    pub use update_status_effects::update_status_effects;
+    pub use update_turrets::update_turrets;
 }
 
 pub mod gen_map {
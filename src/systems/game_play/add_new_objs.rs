@@ -17,6 +17,7 @@ pub fn add_new_objs(
             new_obj.pos,
             new_obj.direction,
             new_obj.speed,
+            new_obj.owner,
             world_info.as_ref(),
             game_map.as_mut(),
             game_obj_lib.as_mut(),
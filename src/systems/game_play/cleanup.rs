@@ -23,7 +23,7 @@ pub fn cleanup(
         if obj.is_ai_bot() {
             let _ = game_info.dec_ai_bot_count();
         } else if obj.is_player() {
-            game_info.clear_player();
+            game_info.remove_player(*entity);
         }
     }
 
@@ -0,0 +1,206 @@
+use crate::game::{
+    DamageDealt, GameObjState, MapPos,
+    components::{AiBotComponent, StatusComponent},
+};
+use crate::game_utils::{FogMap, GameLib, GameMap, GameObjLib, WorldInfo};
+use crate::misc::AppState;
+use bevy::prelude::*;
+
+// Below the fog so that the explored but unseen cells dim them
+const HP_BAR_Z: f32 = 1.7;
+const DAMAGE_TEXT_Z: f32 = 1.8;
+const DAMAGE_TEXT_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+const DAMAGE_TEXT_DURATION: f32 = 0.8;
+const DAMAGE_TEXT_RISE_SPEED: f32 = 40.0;
+const HP_BAR_SIZE: Vec2 = Vec2::new(30.0, 4.0);
+const HP_BAR_OFFSET: f32 = 24.0; // above the bot center
+const HP_BAR_BACKGROUND_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+const HP_BAR_FILL_COLOR: Color = Color::srgb(0.9, 0.2, 0.2);
+const HP_BAR_DURATION: f32 = 3.0;
+const FADE_DURATION: f32 = 0.5;
+
+#[derive(Component)]
+pub struct DamageText {
+    pos: Vec2,
+    map_pos: MapPos, // the cell that was hit, which decides whether the fog hides it
+    remaining: f32,
+}
+
+#[derive(Component)]
+pub struct HpBar {
+    target: Entity,
+    fill: Entity,
+    remaining: f32,
+}
+
+pub fn spawn_combat_feedback(
+    mut damage_reader: MessageReader<DamageDealt>,
+    mut status_query: Query<&mut StatusComponent>,
+    mut hp_bar_query: Query<&mut HpBar>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    game_obj_lib: Res<GameObjLib>,
+    mut commands: Commands,
+) {
+    for damage_dealt in damage_reader.read() {
+        let screen_pos = world_info.get_screen_pos(&damage_dealt.pos);
+        commands.spawn((
+            DespawnOnExit(AppState::Game),
+            DamageText {
+                pos: damage_dealt.pos,
+                map_pos: MapPos {
+                    row: game_map.get_row(damage_dealt.pos.y),
+                    col: game_map.get_col(damage_dealt.pos.x),
+                },
+                remaining: DAMAGE_TEXT_DURATION,
+            },
+            Text2d::new(format!("{:.0}", damage_dealt.damage.ceil())),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(DAMAGE_TEXT_COLOR),
+            Transform::from_xyz(screen_pos.x, screen_pos.y, DAMAGE_TEXT_Z),
+        ));
+
+        if let Ok(mut status_comp) = status_query.get_mut(damage_dealt.entity) {
+            status_comp.flash();
+        }
+
+        let Some(obj) = game_obj_lib.get(&damage_dealt.entity) else {
+            continue;
+        };
+        if !obj.is_ai_bot() || obj.state != GameObjState::Alive {
+            continue;
+        }
+
+        match hp_bar_query
+            .iter_mut()
+            .find(|hp_bar| hp_bar.target == damage_dealt.entity)
+        {
+            Some(mut hp_bar) => hp_bar.remaining = HP_BAR_DURATION,
+            None => {
+                let fill = commands
+                    .spawn((
+                        Sprite::from_color(HP_BAR_FILL_COLOR, HP_BAR_SIZE),
+                        Transform::from_xyz(0.0, 0.0, 0.01),
+                    ))
+                    .id();
+                commands
+                    .spawn((
+                        DespawnOnExit(AppState::Game),
+                        HpBar {
+                            target: damage_dealt.entity,
+                            fill,
+                            remaining: HP_BAR_DURATION,
+                        },
+                        Sprite::from_color(HP_BAR_BACKGROUND_COLOR, HP_BAR_SIZE),
+                        Transform::from_xyz(screen_pos.x, screen_pos.y + HP_BAR_OFFSET, HP_BAR_Z),
+                        Visibility::Hidden,
+                    ))
+                    .add_child(fill);
+            }
+        }
+    }
+}
+
+pub fn update_combat_feedback(
+    mut text_query: Query<(
+        Entity,
+        &mut DamageText,
+        &mut Transform,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+    mut hp_bar_query: Query<
+        (
+            Entity,
+            &mut HpBar,
+            &mut Transform,
+            &mut Visibility,
+            &mut Sprite,
+        ),
+        Without<DamageText>,
+    >,
+    mut fill_query: Query<(&mut Transform, &mut Sprite), (Without<HpBar>, Without<DamageText>)>,
+    bot_query: Query<(), With<AiBotComponent>>,
+    world_info: Res<WorldInfo>,
+    game_obj_lib: Res<GameObjLib>,
+    game_lib: Res<GameLib>,
+    fog_map: Option<Res<FogMap>>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut damage_text, mut transform, mut text_color, mut visibility) in
+        text_query.iter_mut()
+    {
+        damage_text.remaining -= time.delta_secs();
+        if damage_text.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        damage_text.pos.y += DAMAGE_TEXT_RISE_SPEED * time.delta_secs();
+        let screen_pos = world_info.get_screen_pos(&damage_text.pos);
+        transform.translation.x = screen_pos.x;
+        transform.translation.y = screen_pos.y;
+        text_color.set_alpha(fade_alpha(damage_text.remaining));
+        *visibility = if fog_map
+            .as_ref()
+            .is_none_or(|fog_map| fog_map.is_visible(&damage_text.map_pos))
+        {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for (entity, mut hp_bar, mut transform, mut visibility, mut sprite) in hp_bar_query.iter_mut() {
+        hp_bar.remaining -= time.delta_secs();
+        let target = game_obj_lib
+            .get(&hp_bar.target)
+            .filter(|obj| obj.state == GameObjState::Alive);
+        let Some(obj) = target.filter(|_| bot_query.contains(hp_bar.target)) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if hp_bar.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let screen_pos = world_info.get_screen_pos(&obj.pos);
+        transform.translation.x = screen_pos.x;
+        transform.translation.y = screen_pos.y + HP_BAR_OFFSET;
+        *visibility = if world_info.check_pos_visible(&obj.pos)
+            && fog_map
+                .as_ref()
+                .is_none_or(|fog_map| fog_map.is_visible(&obj.map_pos))
+        {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        let alpha = fade_alpha(hp_bar.remaining);
+        sprite.color.set_alpha(alpha);
+
+        let Ok((mut fill_transform, mut fill_sprite)) = fill_query.get_mut(hp_bar.fill) else {
+            continue;
+        };
+        let max_hp = game_lib
+            .get_game_obj_config(obj.config_index)
+            .bot_config()
+            .map_or(1.0, |config| config.hp);
+        let ratio = (obj.hp.unwrap_or(0.0) / max_hp).clamp(0.0, 1.0);
+        let width = HP_BAR_SIZE.x * ratio;
+        fill_sprite.custom_size = Some(Vec2::new(width, HP_BAR_SIZE.y));
+        fill_sprite.color.set_alpha(alpha);
+        fill_transform.translation.x = (width - HP_BAR_SIZE.x) / 2.0;
+    }
+}
+
+#[inline]
+fn fade_alpha(remaining: f32) -> f32 {
+    (remaining / FADE_DURATION).min(1.0)
+}
@@ -0,0 +1,33 @@
+use crate::game::components::{PathComponent, PlayerComponent};
+use crate::game_utils::{GameObjLib, WorldInfo};
+use bevy::prelude::*;
+
+const PATH_COLOR: Color = Color::srgba(0.3, 0.9, 0.3, 0.6);
+const WAYPOINT_RADIUS: f32 = 4.0;
+
+pub fn draw_paths(
+    path_query: Query<(Entity, &PathComponent), With<PlayerComponent>>,
+    world_info: Res<WorldInfo>,
+    game_obj_lib: Res<GameObjLib>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, path_comp) in path_query.iter() {
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+
+        if path_comp.waypoints().is_empty() {
+            continue;
+        }
+
+        let points: Vec<Vec2> = std::iter::once(&obj.pos)
+            .chain(path_comp.waypoints().iter())
+            .map(|pos| world_info.get_screen_pos(pos))
+            .collect();
+
+        gizmos.linestrip_2d(points.iter().copied(), PATH_COLOR);
+        for point in points.iter().skip(1) {
+            gizmos.circle_2d(*point, WAYPOINT_RADIUS, PATH_COLOR);
+        }
+    }
+}
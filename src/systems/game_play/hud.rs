@@ -0,0 +1,74 @@
+use crate::game::components::{StatusComponent, WeaponComponent};
+use crate::game_utils::{GameInfo, GameLib, GameObjLib};
+use crate::misc::AppState;
+use bevy::prelude::*;
+
+const HUD_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+#[derive(Component)]
+pub struct AmmoText;
+
+pub fn setup_hud(mut commands: Commands) {
+    commands.spawn((
+        DespawnOnExit(AppState::Game),
+        Node {
+            position_type: PositionType::Absolute,
+            left: px(10),
+            bottom: px(10),
+            ..default()
+        },
+        children![(
+            AmmoText,
+            Text::new(""),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(HUD_TEXT_COLOR),
+        )],
+    ));
+}
+
+pub fn update_hud(
+    mut ammo_text: Single<&mut Text, With<AmmoText>>,
+    weapon_query: Query<(&WeaponComponent, &StatusComponent)>,
+    game_info: Res<GameInfo>,
+    game_obj_lib: Res<GameObjLib>,
+    game_lib: Res<GameLib>,
+) {
+    let Some(player) = game_info.get_player() else {
+        ammo_text.0.clear();
+        return;
+    };
+    let (Ok((weapon_comp, status_comp)), Some(obj)) =
+        (weapon_query.get(player), game_obj_lib.get(&player))
+    else {
+        return;
+    };
+    let Ok(config) = game_lib.get_game_obj_config(obj.config_index).bot_config() else {
+        return;
+    };
+    let Some(weapon_config) = config.weapon_configs.get(weapon_comp.weapon_index) else {
+        return;
+    };
+
+    let ammo = match weapon_comp.magazine() {
+        Some(magazine) if magazine.reloading => "Reloading...".to_string(),
+        Some(magazine) => match magazine.reserve {
+            Some(reserve) => format!("{}/{} | {}", magazine.rounds, magazine.size, reserve),
+            None => format!("{}/{}", magazine.rounds, magazine.size),
+        },
+        None => "-".to_string(),
+    };
+
+    ammo_text.0 = format!("{}  {}", weapon_config.name, ammo);
+    for name in status_comp.names() {
+        ammo_text.0.push_str("  ");
+        ammo_text.0.push_str(name);
+    }
+    if game_info.waves_remaining() > 0 {
+        ammo_text
+            .0
+            .push_str(&format!("  Waves left: {}", game_info.waves_remaining()));
+    }
+}
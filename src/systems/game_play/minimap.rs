@@ -0,0 +1,216 @@
+use crate::config::InputAction;
+use crate::game::{
+    GameObjType, MapPos,
+    components::{AiBotComponent, InView},
+};
+use crate::game_utils::{ActionState, FogMap, FogState, GameInfo, GameMap, GameObjLib, WorldInfo};
+use crate::misc::AppState;
+use bevy::{
+    asset::RenderAssetUsages,
+    image::ImageSampler,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+const MINIMAP_SIZE: f32 = 200.0; // pixels along the longer side of the map
+const MINIMAP_EMPTY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const MINIMAP_TILE_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+const MINIMAP_GOODIE_COLOR: Color = Color::srgb(0.2, 0.5, 1.0);
+const MINIMAP_PLAYER_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+const MINIMAP_ENEMY_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+const MINIMAP_FRAME_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+#[derive(Component)]
+pub struct Minimap {
+    image: Handle<Image>,
+    row_count: usize,
+    col_count: usize,
+    cell_colors: Vec<Color>, // the static map without dots, row-major from the bottom
+    dots: Vec<MapPos>,       // cells painted with dots on the last update
+}
+
+#[derive(Component)]
+pub struct MinimapFrame;
+
+pub fn setup_minimap(
+    game_map: Option<Res<GameMap>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let Some(game_map) = game_map else {
+        return;
+    };
+    let row_count = game_map.row_count();
+    let col_count = game_map.col_count();
+    let scale = MINIMAP_SIZE / row_count.max(col_count) as f32;
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: col_count as u32,
+            height: row_count as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &MINIMAP_EMPTY_COLOR.to_srgba().to_u8_array(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::nearest();
+    let image = images.add(image);
+
+    commands.spawn((
+        DespawnOnExit(AppState::Game),
+        Minimap {
+            image: image.clone(),
+            row_count,
+            col_count,
+            cell_colors: vec![MINIMAP_EMPTY_COLOR; row_count * col_count],
+            dots: vec![],
+        },
+        ImageNode::new(image),
+        Node {
+            position_type: PositionType::Absolute,
+            right: px(10),
+            top: px(10),
+            width: px(col_count as f32 * scale),
+            height: px(row_count as f32 * scale),
+            ..default()
+        },
+        children![(
+            MinimapFrame,
+            Node {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(px(1)),
+                ..default()
+            },
+            BorderColor::all(MINIMAP_FRAME_COLOR),
+        )],
+    ));
+}
+
+pub fn update_minimap(
+    minimap_query: Single<(&mut Minimap, &mut Visibility)>,
+    mut frame_node: Single<&mut Node, With<MinimapFrame>>,
+    enemy_query: Query<Entity, (With<AiBotComponent>, With<InView>)>,
+    action_state: Res<ActionState>,
+    game_info: Res<GameInfo>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    mut fog_map: Option<ResMut<FogMap>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (mut minimap, mut visibility) = minimap_query.into_inner();
+    let newly_explored = fog_map
+        .as_mut()
+        .map(|fog_map| fog_map.take_newly_explored())
+        .unwrap_or_default();
+    let fog_map = fog_map.as_deref();
+
+    if action_state.just_pressed(InputAction::ToggleMinimap) {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+
+    let Some(image) = images.get_mut(&minimap.image) else {
+        return;
+    };
+
+    // Changes are applied even while hidden so the map is current when shown again
+    for map_pos in game_obj_lib.take_map_changes() {
+        let color = cell_color(&map_pos, game_map.as_ref(), game_obj_lib.as_ref());
+        let index = map_pos.row * minimap.col_count + map_pos.col;
+        minimap.cell_colors[index] = color;
+        minimap.paint(image, &map_pos, None, fog_map);
+    }
+    for map_pos in newly_explored.iter() {
+        minimap.paint(image, map_pos, None, fog_map);
+    }
+
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    let old_dots = std::mem::take(&mut minimap.dots);
+    for map_pos in old_dots.iter() {
+        minimap.paint(image, map_pos, None, fog_map);
+    }
+
+    let enemies = enemy_query
+        .iter()
+        .filter(|entity| {
+            fog_map.is_none_or(|fog_map| {
+                game_obj_lib
+                    .get(entity)
+                    .is_some_and(|obj| fog_map.is_visible(&obj.map_pos))
+            })
+        })
+        .map(|entity| (entity, MINIMAP_ENEMY_COLOR));
+    let players = [game_info.get_player(), game_info.get_peer()]
+        .into_iter()
+        .flatten()
+        .map(|entity| (entity, MINIMAP_PLAYER_COLOR));
+    for (entity, color) in enemies.chain(players) {
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+        minimap.paint(image, &obj.map_pos, Some(color), fog_map);
+        minimap.dots.push(obj.map_pos);
+    }
+
+    let world_width = world_info.world_width();
+    let world_height = world_info.world_height();
+    let region = world_info.visible_region();
+    frame_node.left = percent(region.left / world_width * 100.0);
+    frame_node.bottom = percent(region.bottom / world_height * 100.0);
+    frame_node.width = percent((region.right - region.left) / world_width * 100.0);
+    frame_node.height = percent((region.top - region.bottom) / world_height * 100.0);
+}
+
+impl Minimap {
+    // Paints the cell with the dot color, or restores the static map if None;
+    // unexplored cells are drawn empty
+    fn paint(
+        &self,
+        image: &mut Image,
+        map_pos: &MapPos,
+        dot_color: Option<Color>,
+        fog_map: Option<&FogMap>,
+    ) {
+        if map_pos.row >= self.row_count || map_pos.col >= self.col_count {
+            return;
+        }
+        let unexplored =
+            fog_map.is_some_and(|fog_map| fog_map.state(map_pos) == FogState::Unexplored);
+        let color = match dot_color {
+            Some(color) => color,
+            None if unexplored => MINIMAP_EMPTY_COLOR,
+            None => self.cell_colors[map_pos.row * self.col_count + map_pos.col],
+        };
+        // Image rows run from the top while map rows run from the bottom
+        let _ = image.set_color_at(
+            map_pos.col as u32,
+            (self.row_count - 1 - map_pos.row) as u32,
+            color,
+        );
+    }
+}
+
+fn cell_color(map_pos: &MapPos, game_map: &GameMap, game_obj_lib: &GameObjLib) -> Color {
+    let mut color = MINIMAP_EMPTY_COLOR;
+
+    for entity in game_map.map[map_pos.row][map_pos.col].iter() {
+        let Some(obj) = game_obj_lib.get(entity) else {
+            continue;
+        };
+        match obj.obj_type {
+            GameObjType::Tile if obj.is_collidable() => return MINIMAP_TILE_COLOR,
+            GameObjType::Goodie => color = MINIMAP_GOODIE_COLOR,
+            _ => {}
+        }
+    }
+
+    color
+}
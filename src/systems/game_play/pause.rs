@@ -0,0 +1,46 @@
+use crate::config::InputAction;
+use crate::game_utils::ActionState;
+use crate::misc::GameState;
+use crate::net::NetSession;
+use bevy::prelude::*;
+
+const PAUSE_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+
+pub fn pause(mut commands: Commands) {
+    commands.spawn((
+        DespawnOnExit(GameState::Paused),
+        Node {
+            width: percent(100),
+            height: percent(100),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(PAUSE_BACKGROUND_COLOR),
+        children![(
+            Text::new("Paused"),
+            TextFont {
+                font_size: 33.0,
+                ..default()
+            },
+        )],
+    ));
+}
+
+pub fn toggle_pause(
+    action_state: Res<ActionState>,
+    net_session: Option<Res<NetSession>>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    // A lockstep game can't be paused by one peer alone
+    if net_session.is_some() || !action_state.just_pressed(InputAction::Pause) {
+        return;
+    }
+
+    match game_state.get() {
+        GameState::Play => next_game_state.set(GameState::Paused),
+        GameState::Paused => next_game_state.set(GameState::Play),
+        _ => {}
+    }
+}
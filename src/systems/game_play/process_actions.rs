@@ -0,0 +1,80 @@
+use crate::config::{ControlScheme, InputAction};
+use crate::game::WeaponSwitch;
+use crate::game_utils::{ActionState, GameLib, LocalInput, WorldInfo};
+use crate::misc::translate_cursor_pos;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub fn process_actions(
+    action_state: Res<ActionState>,
+    window_query: Single<&Window, With<PrimaryWindow>>,
+    camera_query: Single<(&Camera, &GlobalTransform)>,
+    world_info: Res<WorldInfo>,
+    game_lib: Res<GameLib>,
+    mut local_input: ResMut<LocalInput>,
+) {
+    let control_scheme = game_lib.game_config.control_scheme;
+
+    if action_state.pressed(InputAction::Fire) {
+        local_input.fire = true;
+    } else if control_scheme == ControlScheme::MouseSteer
+        && action_state.just_pressed(InputAction::Stop)
+    {
+        local_input.stop = true;
+    }
+
+    if control_scheme == ControlScheme::Keyboard {
+        let direction = Vec2::new(
+            action_state.axis(InputAction::MoveLeft, InputAction::MoveRight),
+            action_state.axis(InputAction::MoveDown, InputAction::MoveUp),
+        )
+        .clamp_length_max(1.0);
+        local_input.move_direction = Some([direction.x, direction.y]);
+    }
+
+    if action_state.just_pressed(InputAction::Reload) {
+        local_input.reload = true;
+    }
+
+    for input_config in game_lib.input_configs().iter() {
+        if let InputAction::SelectWeapon(index) = input_config.action
+            && action_state.just_pressed(input_config.action)
+        {
+            local_input.switch_weapon = Some(WeaponSwitch::Select(index));
+        }
+    }
+
+    if action_state.just_pressed(InputAction::NextWeapon) {
+        local_input.switch_weapon = Some(WeaponSwitch::Next);
+    } else if action_state.just_pressed(InputAction::PrevWeapon) {
+        local_input.switch_weapon = Some(WeaponSwitch::Prev);
+    }
+
+    let move_pressed = control_scheme == ControlScheme::MouseSteer
+        && action_state.just_pressed(InputAction::MoveTo);
+    let mark_pressed = action_state.just_pressed(InputAction::MarkTarget);
+    if !move_pressed && !mark_pressed {
+        return;
+    }
+
+    let Some(cursor_pos) = window_query.cursor_position() else {
+        warn!("Failed to get cursor position");
+        return;
+    };
+    let Some(cursor_pos) = translate_cursor_pos(
+        cursor_pos,
+        camera_query.0,
+        camera_query.1,
+        world_info.as_ref(),
+    ) else {
+        return;
+    };
+
+    if move_pressed {
+        local_input.move_target = Some([cursor_pos.x, cursor_pos.y]);
+        local_input.queue_move = action_state.pressed(InputAction::QueueWaypoint);
+    }
+    if mark_pressed {
+        local_input.mark_target = Some([cursor_pos.x, cursor_pos.y]);
+    }
+}
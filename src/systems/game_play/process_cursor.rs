@@ -1,24 +1,13 @@
-use crate::game::{GameObjState, components::PlayerComponent};
-use crate::game_utils::{GameObjLib, WorldInfo};
-use crate::misc::{get_rotation, translate_cursor_pos};
+use crate::game_utils::{LocalInput, WorldInfo};
+use crate::misc::translate_cursor_pos;
 use bevy::prelude::*;
 
 pub fn process_cursor(
-    mut player_query: Single<(Entity, &mut Transform), With<PlayerComponent>>,
     mut cursor_reader: MessageReader<CursorMoved>,
     camera_query: Single<(&Camera, &GlobalTransform)>,
     world_info: Res<WorldInfo>,
-    mut game_obj_lib: ResMut<GameObjLib>,
+    mut local_input: ResMut<LocalInput>,
 ) {
-    let Some(obj) = game_obj_lib.get_mut(&player_query.0) else {
-        error!("Failed to find obj in GameObjLib");
-        return;
-    };
-
-    if obj.state != GameObjState::Alive {
-        return;
-    }
-
     for cursor_moved in cursor_reader.read() {
         let Some(cursor_pos) = translate_cursor_pos(
             cursor_moved.position,
@@ -29,8 +18,6 @@ pub fn process_cursor(
             return;
         };
 
-        let direction = (cursor_pos - obj.pos).normalize();
-        obj.direction = direction;
-        player_query.1.rotation = get_rotation(&direction);
+        local_input.aim_target = Some([cursor_pos.x, cursor_pos.y]);
     }
 }
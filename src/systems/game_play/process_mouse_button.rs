@@ -1,47 +0,0 @@
-use crate::game::{GameObjState, components::PlayerComponent};
-use crate::game_utils::{GameLib, GameObjLib, WorldInfo};
-use crate::misc::{get_rotation, translate_cursor_pos};
-use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
-
-pub fn process_mouse_button(
-    mut player_query: Single<(Entity, &mut Transform), With<PlayerComponent>>,
-    mouse_button_input: Res<ButtonInput<MouseButton>>,
-    window_query: Single<&Window, With<PrimaryWindow>>,
-    camera_query: Single<(&Camera, &GlobalTransform)>,
-    world_info: Res<WorldInfo>,
-    mut game_obj_lib: ResMut<GameObjLib>,
-    game_lib: Res<GameLib>,
-) {
-    if mouse_button_input.just_pressed(MouseButton::Right) {
-        let Some(obj) = game_obj_lib.get_mut(&player_query.0) else {
-            error!("Failed to find obj in GameObjLib");
-            return;
-        };
-
-        if obj.state != GameObjState::Alive {
-            return;
-        }
-
-        let Some(cursor_pos) = window_query.cursor_position() else {
-            warn!("Failed to get cursor position");
-            return;
-        };
-        let Some(cursor_pos) = translate_cursor_pos(
-            cursor_pos,
-            camera_query.0,
-            camera_query.1,
-            world_info.as_ref(),
-        ) else {
-            return;
-        };
-        let Ok(config) = game_lib.get_game_obj_config(obj.config_index).bot_config() else {
-            return;
-        };
-        let direction = (cursor_pos - obj.pos).normalize();
-
-        obj.direction = direction;
-        obj.speed = Some(config.speed);
-        player_query.1.rotation = get_rotation(&direction);
-    }
-}
@@ -0,0 +1,14 @@
+use crate::game_utils::WorldInfo;
+use bevy::{prelude::*, window::WindowResized};
+
+// The visible region is recomputed and re-culled by update_origin
+pub fn resize_window(
+    mut resize_reader: MessageReader<WindowResized>,
+    mut world_info: ResMut<WorldInfo>,
+) {
+    let Some(resized) = resize_reader.read().last() else {
+        return;
+    };
+
+    world_info.set_window_size(resized.width, resized.height);
+}
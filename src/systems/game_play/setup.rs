@@ -1,16 +1,20 @@
 use crate::config::{GameConfig, GameMapConfig};
 use crate::game::create_obj_by_config;
 use crate::game_utils::{
-    DespawnPool, GameInfo, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo,
+    ActionState, DespawnPool, GameInfo, GameLib, GameMap, GameObjLib, LocalInput, NewObjQueue,
+    WorldInfo,
 };
-use crate::misc::{Args, GameState, arr_to_vec2, read_json};
-use bevy::prelude::*;
+use crate::misc::{Args, GameState, arr_to_vec2, read_json, seed_game_rng};
+use crate::net::{NET_TICK_SECS, NetSession};
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use std::time::Duration;
 
 const PLAYER_CONFIG_NAME: &str = "player_bot";
 
 pub fn setup_game(
     args: Res<Args>,
     game_lib: Res<GameLib>,
+    window: Single<&Window>,
     mut commands: Commands,
     mut exit_app: MessageWriter<AppExit>,
     mut game_state: ResMut<NextState<GameState>>,
@@ -19,11 +23,24 @@ pub fn setup_game(
     let Some(map_config) = read_map_config(args.as_ref(), game_config, &mut exit_app) else {
         return;
     };
-    let Some(mut world_info) = create_world_info(game_config, &map_config, &mut exit_app) else {
+    let net_mode = args.net_peer.is_some();
+    let local_player_slot = if net_mode && !args.net_host { 1 } else { 0 };
+    let Some(mut world_info) = create_world_info(
+        game_config,
+        &map_config,
+        &window,
+        net_mode,
+        local_player_slot,
+        &mut exit_app,
+    ) else {
         return;
     };
     let mut game_obj_lib = GameObjLib::new();
-    let mut game_info = GameInfo::new();
+    let mut game_info = GameInfo::new(local_player_slot);
+
+    if !setup_net(args.as_ref(), &mut commands, &mut exit_app) {
+        return;
+    }
 
     let Some(game_map) = load_game_map(
         &map_config,
@@ -44,6 +61,8 @@ pub fn setup_game(
     commands.insert_resource(NewObjQueue::new());
     commands.insert_resource(DespawnPool::new());
     commands.insert_resource(game_info);
+    commands.insert_resource(LocalInput::new());
+    commands.insert_resource(ActionState::new());
 
     game_state.set(GameState::Play);
 
@@ -71,14 +90,46 @@ fn read_map_config(
     Some(map_config)
 }
 
+fn setup_net(args: &Args, commands: &mut Commands, exit_app: &mut MessageWriter<AppExit>) -> bool {
+    let Some(net_peer) = args.net_peer else {
+        seed_game_rng(args.seed.unwrap_or_else(rand::random));
+        return true;
+    };
+    let Some(net_bind) = args.net_bind else {
+        error!("net_bind missing from args");
+        exit_app.write(AppExit::error());
+        return false;
+    };
+
+    match NetSession::new(net_bind, net_peer, args.net_input_delay) {
+        Ok(net_session) => {
+            // Both instances must roll the same numbers, so the seed can't be random here
+            seed_game_rng(args.seed.unwrap_or_default());
+            commands.insert_resource(net_session);
+            commands.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                NET_TICK_SECS,
+            )));
+            true
+        }
+        Err(err) => {
+            error!("Failed to create NetSession: {}", err);
+            exit_app.write(AppExit::error());
+            false
+        }
+    }
+}
+
 fn create_world_info(
     game_config: &GameConfig,
     map_config: &GameMapConfig,
+    window: &Window,
+    net_mode: bool,
+    local_player_slot: usize,
     exit_app: &mut MessageWriter<AppExit>,
 ) -> Option<WorldInfo> {
     let world_width = game_config.cell_size * map_config.col_count as f32;
     let world_height = game_config.cell_size * map_config.row_count as f32;
-    let Some(player_pos) = find_player_pos(map_config) else {
+    let Some(player_pos) = find_player_pos(map_config, local_player_slot) else {
         error!("Cannot find player in map");
         exit_app.write(AppExit::error());
         return None;
@@ -86,9 +137,10 @@ fn create_world_info(
     let world_info = WorldInfo::new(
         world_width,
         world_height,
-        game_config.window_width(),
-        game_config.window_height(),
+        window.width(),
+        window.height(),
         game_config.window_ext_size,
+        net_mode,
         &player_pos,
     );
 
@@ -126,12 +178,11 @@ fn load_game_map(
     Some(game_map)
 }
 
-fn find_player_pos(map_config: &GameMapConfig) -> Option<Vec2> {
-    for obj_config in map_config.objs.iter() {
-        if obj_config.config_name == PLAYER_CONFIG_NAME {
-            return Some(arr_to_vec2(&obj_config.pos));
-        }
-    }
-
-    None
+fn find_player_pos(map_config: &GameMapConfig, player_slot: usize) -> Option<Vec2> {
+    map_config
+        .objs
+        .iter()
+        .filter(|obj_config| obj_config.config_name == PLAYER_CONFIG_NAME)
+        .nth(player_slot)
+        .map(|obj_config| arr_to_vec2(&obj_config.pos))
 }
@@ -0,0 +1,64 @@
+use crate::game::components::{SpawnerComponent, StatusComponent, WeaponComponent};
+use crate::game_utils::{GameObjLib, LocalInput};
+use crate::misc::GameState;
+use crate::net::NetSession;
+use bevy::prelude::*;
+
+pub fn sync_net(
+    mut net_session: ResMut<NetSession>,
+    mut local_input: ResMut<LocalInput>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if let Err(err) = net_session.poll() {
+        error!("Failed to receive from peer: {}", err);
+    }
+
+    if net_session.is_peer_timed_out() {
+        error!("Peer timed out, ending the game");
+        game_state.set(GameState::GameOver);
+        return;
+    }
+
+    if net_session.is_desynced() {
+        error!("Simulation diverged from the peer, ending the game");
+        game_state.set(GameState::GameOver);
+        return;
+    }
+
+    if net_session.needs_local_input() {
+        net_session.schedule_local_input(local_input.take());
+    }
+
+    if let Err(err) = net_session.send_inputs() {
+        error!("Failed to send inputs to peer: {}", err);
+    }
+}
+
+pub fn finish_net_tick(
+    status_query: Query<&StatusComponent>,
+    weapon_query: Query<&WeaponComponent>,
+    spawner_query: Query<&SpawnerComponent>,
+    mut net_session: ResMut<NetSession>,
+    game_obj_lib: Res<GameObjLib>,
+) {
+    let state_hash = game_obj_lib.state_hash(|entity, hasher| {
+        if let Ok(status_comp) = status_query.get(*entity) {
+            status_comp.hash_state(hasher);
+        }
+        if let Ok(weapon_comp) = weapon_query.get(*entity) {
+            weapon_comp.hash_state(hasher);
+        }
+        if let Ok(spawner_comp) = spawner_query.get(*entity) {
+            spawner_comp.hash_state(hasher);
+        }
+    });
+    if let Err(err) = net_session.finish_tick(state_hash) {
+        error!("Failed to finish tick: {}", err);
+    }
+}
+
+// Without a session every frame is a tick; with one the simulation waits
+// until the peer's input for the current tick has arrived.
+pub fn net_tick_ready(net_session: Option<Res<NetSession>>) -> bool {
+    net_session.is_none_or(|session| session.is_ready())
+}
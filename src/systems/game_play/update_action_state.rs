@@ -0,0 +1,72 @@
+use crate::config::{InputBinding, WheelDirection};
+use crate::game_utils::{ActionState, GameLib};
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+
+pub fn update_action_state(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    mut mouse_wheel_reader: MessageReader<MouseWheel>,
+    gamepads: Query<&Gamepad>,
+    game_lib: Res<GameLib>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let (wheel_up, wheel_down) = mouse_wheel_reader
+        .read()
+        .fold((false, false), |(up, down), mouse_wheel| {
+            (up || mouse_wheel.y > 0.0, down || mouse_wheel.y < 0.0)
+        });
+
+    action_state.begin_frame();
+
+    for input_config in game_lib.input_configs().iter() {
+        let action = input_config.action;
+
+        for binding in input_config.bindings.iter() {
+            match binding {
+                InputBinding::Key(key) => {
+                    // A press and release within one frame still counts
+                    if key_input.just_pressed(*key) {
+                        action_state.trigger(action);
+                    } else if key_input.pressed(*key) {
+                        action_state.set(action, 1.0);
+                    }
+                }
+                InputBinding::Mouse(button) => {
+                    if mouse_button_input.just_pressed(*button) {
+                        action_state.trigger(action);
+                    } else if mouse_button_input.pressed(*button) {
+                        action_state.set(action, 1.0);
+                    }
+                }
+                InputBinding::MouseWheel(WheelDirection::Up) => {
+                    if wheel_up {
+                        action_state.trigger(action);
+                    }
+                }
+                InputBinding::MouseWheel(WheelDirection::Down) => {
+                    if wheel_down {
+                        action_state.trigger(action);
+                    }
+                }
+                InputBinding::GamepadButton(button) => {
+                    for gamepad in gamepads.iter() {
+                        if gamepad.just_pressed(*button) {
+                            action_state.trigger(action);
+                        } else if gamepad.pressed(*button) {
+                            action_state.set(action, 1.0);
+                        }
+                    }
+                }
+                InputBinding::GamepadAxis(axis, sign) => {
+                    for gamepad in gamepads.iter() {
+                        let value = gamepad.get(*axis).unwrap_or(0.0) * sign;
+                        if value > 0.0 {
+                            action_state.set(action, value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
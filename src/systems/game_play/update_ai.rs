@@ -1,8 +1,9 @@
 use crate::game::{
-    GameObjState,
-    components::{AiBotComponent, AiComponent, InView, WeaponComponent},
+    GameObjState, WeaponSwitch,
+    components::{AiBotComponent, AiComponent, WeaponComponent},
+    switch_weapon,
 };
-use crate::game_utils::{GameInfo, GameLib, GameObjLib};
+use crate::game_utils::{GameInfo, GameLib, GameObjLib, WorldInfo};
 use bevy::prelude::*;
 
 pub fn update_ai(
@@ -13,40 +14,77 @@ pub fn update_ai(
             &mut AiComponent,
             &mut Transform,
         ),
-        (With<AiBotComponent>, With<InView>),
+        With<AiBotComponent>,
     >,
     mut game_obj_lib: ResMut<GameObjLib>,
     game_lib: Res<GameLib>,
     game_info: Res<GameInfo>,
+    world_info: Res<WorldInfo>,
+    mut commands: Commands,
     time: Res<Time>,
 ) {
-    let Some(player_entity) = game_info.get_player() else {
-        return;
-    };
-    let Some(player) = game_obj_lib.get(&player_entity).cloned() else {
-        return;
-    };
+    let players: Vec<Vec2> = [game_info.get_player(), game_info.get_peer()]
+        .into_iter()
+        .flatten()
+        .filter_map(|entity| game_obj_lib.get(&entity))
+        .filter(|obj| obj.state == GameObjState::Alive)
+        .map(|obj| obj.pos)
+        .collect();
 
-    if player.state != GameObjState::Alive {
+    if players.is_empty() {
         return;
     }
 
-    for (entity, mut weapon_comp, mut ai_comp, mut transform) in aibot_query.iter_mut() {
+    for entity in game_obj_lib.in_spawn_order(aibot_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut weapon_comp, mut ai_comp, mut transform)) = aibot_query.get_mut(entity)
+        else {
+            continue;
+        };
         let Some(obj) = game_obj_lib.get_mut(&entity) else {
             continue;
         };
 
-        if obj.state != GameObjState::Alive {
+        if obj.state != GameObjState::Alive || !world_info.check_pos_simulated(&obj.pos) {
             continue;
         }
 
+        let Some(player_pos) = nearest_pos(&obj.pos, &players) else {
+            continue;
+        };
+
         ai_comp.engine.run(
             obj,
             transform.as_mut(),
             weapon_comp.as_mut(),
-            &player.pos,
+            &player_pos,
             game_lib.as_ref(),
             time.as_ref(),
         );
+
+        if let Some(turret) = weapon_comp.turret.as_mut() {
+            turret.target = Some(player_pos);
+        }
+
+        let weapon_index = ai_comp.engine.weapon_index();
+        if weapon_index != weapon_comp.weapon_index {
+            let _ = switch_weapon(
+                entity,
+                WeaponSwitch::Select(weapon_index),
+                weapon_comp.as_mut(),
+                game_obj_lib.as_ref(),
+                game_lib.as_ref(),
+                &mut commands,
+            );
+        }
     }
 }
+
+fn nearest_pos(pos: &Vec2, candidates: &[Vec2]) -> Option<Vec2> {
+    candidates
+        .iter()
+        .min_by(|a, b| {
+            pos.distance_squared(**a)
+                .total_cmp(&pos.distance_squared(**b))
+        })
+        .cloned()
+}
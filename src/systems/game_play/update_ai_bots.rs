@@ -1,7 +1,7 @@
 use crate::ai::AiAction;
 use crate::game::{
     GameObjState, MoveResult,
-    components::{AiBotComponent, AiComponent, InView, WeaponComponent},
+    components::{AiBotComponent, AiComponent, StatusComponent, WeaponComponent},
     move_bot, try_shoot,
 };
 use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
@@ -15,8 +15,9 @@ pub fn update_ai_bots(
             &mut Visibility,
             &mut WeaponComponent,
             &AiComponent,
+            &StatusComponent,
         ),
-        (With<AiBotComponent>, With<InView>),
+        With<AiBotComponent>,
     >,
     world_info: Res<WorldInfo>,
     mut game_map: ResMut<GameMap>,
@@ -27,21 +28,27 @@ pub fn update_ai_bots(
     mut commands: Commands,
     time: Res<Time>,
 ) {
-    for (entity, mut transform, mut visibility, mut weapon_comp, ai_comp) in ai_bot_query.iter_mut()
-    {
+    for entity in game_obj_lib.in_spawn_order(ai_bot_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut transform, mut visibility, mut weapon_comp, ai_comp, status_comp)) =
+            ai_bot_query.get_mut(entity)
+        else {
+            continue;
+        };
         let Some(obj) = game_obj_lib.get(&entity) else {
             continue;
         };
 
-        if obj.state != GameObjState::Alive {
+        if obj.state != GameObjState::Alive || !world_info.check_pos_simulated(&obj.pos) {
             continue;
         }
 
+        weapon_comp.update_reload(time.as_ref());
+
         match ai_comp.engine.cur_action() {
-            AiAction::Chase => {
+            AiAction::Chase if !status_comp.is_stunned() => {
                 match move_bot(
                     entity,
-                    obj.speed.unwrap_or(0.0),
+                    obj.speed.unwrap_or(0.0) * status_comp.speed_multiplier(),
                     transform.as_mut(),
                     visibility.as_mut(),
                     world_info.as_ref(),
@@ -63,15 +70,17 @@ pub fn update_ai_bots(
                     _ => {}
                 }
             }
-            AiAction::Shoot => {
+            AiAction::Shoot if !status_comp.is_disarmed() => {
                 let _ = try_shoot(
                     entity,
                     obj.speed.unwrap_or(0.0),
                     weapon_comp.as_mut(),
                     world_info.as_ref(),
+                    game_map.as_ref(),
                     game_obj_lib.as_mut(),
                     game_lib.as_ref(),
                     new_obj_queue.as_mut(),
+                    &mut commands,
                     time.as_ref(),
                 );
             }
@@ -0,0 +1,21 @@
+use crate::game::components::BeamComponent;
+use crate::game_utils::WorldInfo;
+use bevy::prelude::*;
+
+pub fn update_beams(
+    mut beam_query: Query<(Entity, &mut Transform, &mut BeamComponent)>,
+    world_info: Res<WorldInfo>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut beam_comp) in beam_query.iter_mut() {
+        if beam_comp.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let screen_pos = world_info.get_screen_pos(&beam_comp.pos);
+        transform.translation.x = screen_pos.x;
+        transform.translation.y = screen_pos.y;
+    }
+}
@@ -0,0 +1,38 @@
+use crate::config::InputAction;
+use crate::game_utils::{ActionState, GameLib, WorldInfo};
+use bevy::prelude::*;
+
+pub fn update_camera(
+    action_state: Res<ActionState>,
+    game_lib: Res<GameLib>,
+    mut world_info: ResMut<WorldInfo>,
+    mut projection: Single<&mut Projection, With<Camera2d>>,
+) {
+    let camera_config = &game_lib.game_config.camera;
+    let mut zoom = world_info.zoom();
+
+    if action_state.just_pressed(InputAction::ZoomIn) {
+        zoom *= camera_config.zoom_step;
+    }
+    if action_state.just_pressed(InputAction::ZoomOut) {
+        zoom /= camera_config.zoom_step;
+    }
+
+    let zoom = zoom.clamp(camera_config.min_zoom, camera_config.max_zoom);
+    if zoom == world_info.zoom() {
+        return;
+    }
+
+    world_info.set_zoom(zoom);
+    set_projection_scale(projection.as_mut(), 1.0 / zoom);
+}
+
+pub fn reset_camera(mut projection: Single<&mut Projection, With<Camera2d>>) {
+    set_projection_scale(projection.as_mut(), 1.0);
+}
+
+fn set_projection_scale(projection: &mut Projection, scale: f32) {
+    if let Projection::Orthographic(orthographic) = projection {
+        orthographic.scale = scale;
+    }
+}
@@ -0,0 +1,107 @@
+use crate::game::{
+    GameObjState,
+    components::{DeviceComponent, WeaponComponent},
+    find_nearest_enemy, on_death, try_shoot,
+};
+use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use crate::misc::get_rotation;
+use bevy::prelude::*;
+
+pub fn update_devices(
+    mut device_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut DeviceComponent,
+        Option<&mut WeaponComponent>,
+    )>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut new_obj_queue: ResMut<NewObjQueue>,
+    mut despawn_pool: ResMut<DespawnPool>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for entity in game_obj_lib.in_spawn_order(device_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut transform, mut device_comp, weapon_comp)) = device_query.get_mut(entity)
+        else {
+            continue;
+        };
+        let Some(obj) = game_obj_lib.get(&entity).cloned() else {
+            continue;
+        };
+
+        if obj.state != GameObjState::Alive {
+            continue;
+        }
+
+        // Destroyed by damage without a Phaseout on-death action
+        if obj.hp.is_some_and(|hp| hp <= 0.0) {
+            let _ = despawn_pool.add(entity, game_obj_lib.as_mut());
+            continue;
+        }
+
+        if !device_comp.arming_timer.tick(time.delta()).is_finished() {
+            continue;
+        }
+
+        let Some(target_pos) = find_nearest_enemy(
+            &obj.pos,
+            obj.side,
+            device_comp.trigger_radius,
+            game_map.as_ref(),
+            game_obj_lib.as_ref(),
+            game_lib.as_ref(),
+        )
+        .and_then(|target| game_obj_lib.get(&target).map(|o| o.pos)) else {
+            continue;
+        };
+
+        match weapon_comp {
+            Some(mut weapon_comp) => {
+                weapon_comp.update_reload(time.as_ref());
+                if let Some(turret) = weapon_comp.turret.as_mut() {
+                    turret.target = Some(target_pos);
+                } else if let Some(direction) = (target_pos - obj.pos).try_normalize()
+                    && let Some(obj) = game_obj_lib.get_mut(&entity)
+                {
+                    obj.direction = direction;
+                    transform.rotation = get_rotation(&direction);
+                }
+
+                let _ = try_shoot(
+                    entity,
+                    0.0,
+                    weapon_comp.as_mut(),
+                    world_info.as_ref(),
+                    game_map.as_ref(),
+                    game_obj_lib.as_mut(),
+                    game_lib.as_ref(),
+                    new_obj_queue.as_mut(),
+                    &mut commands,
+                    time.as_ref(),
+                );
+            }
+            None => {
+                if let Some(obj) = game_obj_lib.get_mut(&entity) {
+                    obj.hp = Some(0.0);
+                }
+                let _ = on_death(
+                    entity,
+                    game_map.as_ref(),
+                    game_obj_lib.as_mut(),
+                    game_lib.as_ref(),
+                    new_obj_queue.as_mut(),
+                    &mut commands,
+                );
+                if game_obj_lib
+                    .get(&entity)
+                    .is_some_and(|obj| obj.state == GameObjState::Alive)
+                {
+                    let _ = despawn_pool.add(entity, game_obj_lib.as_mut());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::game::{
+    components::{AiBotComponent, InView},
+    is_blocked,
+};
+use crate::game_utils::{FogMap, FogState, GameInfo, GameLib, GameMap, GameObjLib, WorldInfo};
+use crate::misc::AppState;
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+const FOG_Z: f32 = 2.0;
+const FOG_UNEXPLORED_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 1.0);
+const FOG_EXPLORED_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const FOG_VISIBLE_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.0);
+
+#[derive(Component)]
+pub struct FogOverlay {
+    image: Handle<Image>,
+}
+
+pub fn setup_fog(
+    game_map: Option<Res<GameMap>>,
+    world_info: Option<Res<WorldInfo>>,
+    game_lib: Res<GameLib>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let (Some(game_map), Some(world_info)) = (game_map, world_info) else {
+        return;
+    };
+    if game_lib.game_config.fog_of_war.is_none() {
+        return;
+    }
+    let row_count = game_map.row_count();
+    let col_count = game_map.col_count();
+
+    // One pixel per cell, stretched over the world with linear filtering to soften the edges
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: col_count as u32,
+            height: row_count as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &FOG_UNEXPLORED_COLOR.to_srgba().to_u8_array(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+
+    commands.insert_resource(FogMap::new(row_count, col_count));
+    commands.spawn((
+        DespawnOnExit(AppState::Game),
+        FogOverlay {
+            image: image.clone(),
+        },
+        Sprite {
+            image,
+            custom_size: Some(Vec2::new(
+                world_info.world_width(),
+                world_info.world_height(),
+            )),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, FOG_Z),
+    ));
+}
+
+pub fn update_fog(
+    overlay_query: Single<(&FogOverlay, &mut Transform)>,
+    mut enemy_query: Query<(Entity, &mut Visibility), (With<AiBotComponent>, With<InView>)>,
+    mut fog_map: ResMut<FogMap>,
+    game_info: Res<GameInfo>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    game_obj_lib: Res<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(fog_config) = game_lib.game_config.fog_of_war.as_ref() else {
+        return;
+    };
+    let (overlay, mut transform) = overlay_query.into_inner();
+
+    let world_center = Vec2::new(world_info.world_width(), world_info.world_height()) / 2.0;
+    let screen_pos = world_info.get_screen_pos(&world_center);
+    transform.translation.x = screen_pos.x;
+    transform.translation.y = screen_pos.y;
+
+    if let Some(player) = game_info.get_player().and_then(|e| game_obj_lib.get(&e)) {
+        let radius = (fog_config.vision_range / game_lib.game_config.cell_size).round() as usize;
+        let changes = fog_map.update(player.map_pos, radius, |map_pos| {
+            fog_config.blocked_by_tiles
+                && is_blocked(
+                    (map_pos.row, map_pos.col),
+                    game_map.as_ref(),
+                    game_obj_lib.as_ref(),
+                )
+        });
+
+        if !changes.is_empty()
+            && let Some(image) = images.get_mut(&overlay.image)
+        {
+            for map_pos in changes {
+                let color = match fog_map.state(&map_pos) {
+                    FogState::Unexplored => FOG_UNEXPLORED_COLOR,
+                    FogState::Explored => FOG_EXPLORED_COLOR,
+                    FogState::Visible => FOG_VISIBLE_COLOR,
+                };
+                // Image rows run from the top while map rows run from the bottom
+                let _ = image.set_color_at(
+                    map_pos.col as u32,
+                    (game_map.row_count() - 1 - map_pos.row) as u32,
+                    color,
+                );
+            }
+        }
+    }
+
+    for (entity, mut visibility) in enemy_query.iter_mut() {
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+        let new_visibility = if fog_map.is_visible(&obj.map_pos) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+    }
+}
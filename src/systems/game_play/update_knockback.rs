@@ -0,0 +1,53 @@
+use crate::game::{
+    GameObjState,
+    components::{AiBotComponent, PeerComponent, PlayerComponent},
+    push_bot,
+};
+use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use bevy::prelude::*;
+
+pub fn update_knockback(
+    mut bot_query: Query<
+        (Entity, &mut Transform, &mut Visibility),
+        Or<(
+            With<PlayerComponent>,
+            With<PeerComponent>,
+            With<AiBotComponent>,
+        )>,
+    >,
+    world_info: Res<WorldInfo>,
+    mut game_map: ResMut<GameMap>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut new_obj_queue: ResMut<NewObjQueue>,
+    mut despawn_pool: ResMut<DespawnPool>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for entity in game_obj_lib.in_spawn_order(bot_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut transform, mut visibility)) = bot_query.get_mut(entity) else {
+            continue;
+        };
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+
+        if obj.state != GameObjState::Alive || obj.velocity == Vec2::ZERO {
+            continue;
+        }
+
+        let _ = push_bot(
+            entity,
+            transform.as_mut(),
+            visibility.as_mut(),
+            world_info.as_ref(),
+            game_map.as_mut(),
+            game_obj_lib.as_mut(),
+            game_lib.as_ref(),
+            new_obj_queue.as_mut(),
+            despawn_pool.as_mut(),
+            &mut commands,
+            time.as_ref(),
+        );
+    }
+}
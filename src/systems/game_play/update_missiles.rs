@@ -1,5 +1,7 @@
 use crate::game::{GameObjState, components::MissileComponent, move_missile, on_death};
-use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use crate::game_utils::{
+    DespawnPool, GameInfo, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo,
+};
 use bevy::prelude::*;
 
 pub fn update_missiles(
@@ -8,12 +10,16 @@ pub fn update_missiles(
     mut game_map: ResMut<GameMap>,
     mut game_obj_lib: ResMut<GameObjLib>,
     game_lib: Res<GameLib>,
+    game_info: Res<GameInfo>,
     mut new_obj_queue: ResMut<NewObjQueue>,
     mut despawn_pool: ResMut<DespawnPool>,
     mut commands: Commands,
     time: Res<Time>,
 ) {
-    for (entity, mut transform, mut missile_comp) in missile_query.iter_mut() {
+    for entity in game_obj_lib.in_spawn_order(missile_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut transform, mut missile_comp)) = missile_query.get_mut(entity) else {
+            continue;
+        };
         let Some(obj) = game_obj_lib.get(&entity).cloned() else {
             continue;
         };
@@ -41,12 +47,32 @@ pub fn update_missiles(
             }
         }
 
+        let owner = missile_comp.owner;
+        if let Some(split_ability) = missile_comp.split_ability.as_mut()
+            && let Ok(true) = split_ability.update(
+                entity,
+                owner,
+                game_map.as_ref(),
+                game_obj_lib.as_mut(),
+                game_lib.as_ref(),
+                new_obj_queue.as_mut(),
+                despawn_pool.as_mut(),
+                time.as_ref(),
+            )
+        {
+            continue;
+        }
+
         if let Some(enemy_search_ability) = missile_comp.enemy_search_ability.as_mut() {
+            let marked_target = owner
+                .and_then(|owner| game_info.player_slot(&owner))
+                .and_then(|slot| game_info.get_marked_target(slot));
             let _ = enemy_search_ability.update(
                 &entity,
                 transform.as_mut(),
                 game_map.as_ref(),
                 game_obj_lib.as_mut(),
+                marked_target,
                 time.as_ref(),
             );
         }
@@ -74,6 +100,7 @@ pub fn update_missiles(
             let _ = move_missile(
                 entity,
                 speed,
+                missile_comp.bounce_ability.as_mut(),
                 transform.as_mut(),
                 world_info.as_mut(),
                 game_map.as_mut(),
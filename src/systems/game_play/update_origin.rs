@@ -2,9 +2,11 @@ use crate::game::{
     GameObjState,
     components::{InView, PlayerComponent},
 };
-use crate::game_utils::{DespawnPool, GameMap, GameObjLib, WorldInfo};
+use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, WorldInfo};
 use bevy::{ecs::entity::ContainsEntity, prelude::*};
 
+const MIN_CAMERA_STEP: f32 = 0.5;
+
 pub fn update_origin(
     player_query: Single<Entity, With<PlayerComponent>>,
     mut transform_query: Query<&mut Transform>,
@@ -12,8 +14,10 @@ pub fn update_origin(
     game_map: Res<GameMap>,
     mut world_info: ResMut<WorldInfo>,
     mut game_obj_lib: ResMut<GameObjLib>,
+    game_lib: Res<GameLib>,
     mut despawn_pool: ResMut<DespawnPool>,
     mut commands: Commands,
+    time: Res<Time>,
 ) {
     let Some(player) = game_obj_lib.get(&player_query.entity()) else {
         return;
@@ -21,8 +25,17 @@ pub fn update_origin(
     let old_origin = world_info.origin();
     let old_visible_region = world_info.visible_region().clone();
 
-    world_info.set_origin(&player.pos);
-    if old_origin == world_info.origin() {
+    let camera_config = &game_lib.game_config.camera;
+    let target = player.pos + player.move_velocity * camera_config.lead_time;
+    let new_origin = if old_origin.distance(target) < MIN_CAMERA_STEP {
+        target
+    } else {
+        let t = 1.0 - (-camera_config.follow_rate * time.delta_secs()).exp();
+        old_origin.lerp(target, t)
+    };
+
+    world_info.set_origin(&new_origin);
+    if old_origin == world_info.origin() && old_visible_region == *world_info.visible_region() {
         return;
     }
 
@@ -60,17 +73,21 @@ pub fn update_origin(
             if obj.is_ai_bot() {
                 commands.entity(entity).insert(InView);
             }
-        } else {
-            if obj.is_transient() {
-                let _ = despawn_pool.add(entity, game_obj_lib.as_mut());
-            } else {
+        } else if obj.is_transient() {
+            if world_info.check_pos_simulated(&obj.pos) {
                 let screen_pos = world_info.get_screen_pos(&obj.pos);
                 transform.translation.x = screen_pos.x;
                 transform.translation.y = screen_pos.y;
-                *visibility = Visibility::Hidden;
-                if obj.is_ai_bot() {
-                    commands.entity(entity).remove::<InView>();
-                }
+            } else {
+                let _ = despawn_pool.add(entity, game_obj_lib.as_mut());
+            }
+        } else {
+            let screen_pos = world_info.get_screen_pos(&obj.pos);
+            transform.translation.x = screen_pos.x;
+            transform.translation.y = screen_pos.y;
+            *visibility = Visibility::Hidden;
+            if obj.is_ai_bot() {
+                commands.entity(entity).remove::<InView>();
             }
         }
     }
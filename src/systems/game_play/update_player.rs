@@ -1,9 +1,23 @@
-use crate::game::{GameObjState, components::PlayerComponent, move_bot};
+use crate::game::{
+    GameObjState, MoveResult, capture_goodies,
+    components::{PathComponent, PeerComponent, PlayerComponent, StatusComponent, WeaponComponent},
+    move_bot,
+};
 use crate::game_utils::{DespawnPool, GameLib, GameMap, GameObjLib, NewObjQueue, WorldInfo};
 use bevy::prelude::*;
 
 pub fn update_player(
-    mut player_query: Query<(Entity, &mut Transform, &mut Visibility), With<PlayerComponent>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Visibility,
+            &mut WeaponComponent,
+            &mut PathComponent,
+            &StatusComponent,
+        ),
+        Or<(With<PlayerComponent>, With<PeerComponent>)>,
+    >,
     mut game_map: ResMut<GameMap>,
     world_info: Res<WorldInfo>,
     mut game_obj_lib: ResMut<GameObjLib>,
@@ -13,36 +27,65 @@ pub fn update_player(
     mut commands: Commands,
     time: Res<Time>,
 ) {
-    let Ok((entity, mut transform, mut visibility)) = player_query.single_mut() else {
-        return;
-    };
+    for entity in game_obj_lib.in_spawn_order(player_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut transform, mut visibility, mut weapon_comp, mut path_comp, status_comp)) =
+            player_query.get_mut(entity)
+        else {
+            continue;
+        };
+        let Some(obj) = game_obj_lib.get_mut(&entity) else {
+            error!("Failed to find player in GameObjLib");
+            continue;
+        };
 
-    let Some(obj) = game_obj_lib.get(&entity) else {
-        error!("Failed to find player in GameObjLib");
-        return;
-    };
+        if obj.state != GameObjState::Alive || status_comp.is_stunned() {
+            continue;
+        }
 
-    if obj.state != GameObjState::Alive {
-        return;
-    }
+        if let Ok(config) = game_lib.get_game_obj_config(obj.config_index).bot_config() {
+            path_comp.follow(obj, transform.as_mut(), config.speed);
+        }
+
+        let Some(speed) = obj.speed else {
+            error!("speed is none");
+            continue;
+        };
 
-    let Some(speed) = obj.speed else {
-        error!("speed is none");
-        return;
-    };
+        let collide_span = obj.collide_span;
 
-    let _ = move_bot(
-        entity,
-        speed,
-        transform.as_mut(),
-        visibility.as_mut(),
-        world_info.as_ref(),
-        game_map.as_mut(),
-        game_obj_lib.as_mut(),
-        game_lib.as_ref(),
-        new_obj_queue.as_mut(),
-        despawn_pool.as_mut(),
-        &mut commands,
-        time.as_ref(),
-    );
+        match move_bot(
+            entity,
+            speed * status_comp.speed_multiplier(),
+            transform.as_mut(),
+            visibility.as_mut(),
+            world_info.as_ref(),
+            game_map.as_mut(),
+            game_obj_lib.as_mut(),
+            game_lib.as_ref(),
+            new_obj_queue.as_mut(),
+            despawn_pool.as_mut(),
+            &mut commands,
+            time.as_ref(),
+        ) {
+            Ok(MoveResult::Moved(new_pos)) => {
+                let _ = capture_goodies(
+                    &new_pos,
+                    collide_span,
+                    weapon_comp.as_mut(),
+                    game_map.as_ref(),
+                    game_obj_lib.as_mut(),
+                    game_lib.as_ref(),
+                    despawn_pool.as_mut(),
+                );
+            }
+            // Following the path any further would only keep pushing into the obstacle
+            Ok(MoveResult::Collided) if !path_comp.waypoints().is_empty() => {
+                path_comp.clear();
+                if let Some(obj) = game_obj_lib.get_mut(&entity) {
+                    obj.speed = Some(0.0);
+                }
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,133 @@
+use crate::game::{
+    PlayerInput, apply_player_input,
+    components::{PathComponent, PeerComponent, PlayerComponent, StatusComponent, WeaponComponent},
+    find_marked_target,
+};
+use crate::game_utils::{
+    GameInfo, GameLib, GameMap, GameObjLib, LocalInput, NewObjQueue, WorldInfo,
+};
+use crate::misc::arr_to_vec2;
+use crate::net::NetSession;
+use bevy::prelude::*;
+
+pub fn update_player_input(
+    mut player_query: Query<
+        (
+            &mut Transform,
+            &mut WeaponComponent,
+            &mut PathComponent,
+            &StatusComponent,
+        ),
+        Or<(With<PlayerComponent>, With<PeerComponent>)>,
+    >,
+    mut local_input: ResMut<LocalInput>,
+    net_session: Option<Res<NetSession>>,
+    mut game_info: ResMut<GameInfo>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut new_obj_queue: ResMut<NewObjQueue>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    let (player_input, peer_input) = match net_session.as_ref() {
+        Some(session) => {
+            let Some((local, remote)) = session.current_inputs() else {
+                return;
+            };
+            (local, Some(remote))
+        }
+        None => (local_input.take(), None),
+    };
+
+    let mut inputs = [
+        (game_info.get_player(), Some(player_input)),
+        (game_info.get_peer(), peer_input),
+    ];
+    // Both instances have to apply the inputs in the same order, so it goes by slot
+    if game_info.local_player_slot() != 0 {
+        inputs.swap(0, 1);
+    }
+
+    for (entity, input) in inputs {
+        let (Some(entity), Some(input)) = (entity, input) else {
+            continue;
+        };
+
+        apply_input(
+            entity,
+            &input,
+            &mut player_query,
+            world_info.as_ref(),
+            game_map.as_ref(),
+            game_obj_lib.as_mut(),
+            game_lib.as_ref(),
+            new_obj_queue.as_mut(),
+            &mut commands,
+            time.as_ref(),
+        );
+
+        if let Some(mark_target) = input.mark_target.as_ref()
+            && let Some(obj) = game_obj_lib.get(&entity)
+            && let Some(player_slot) = game_info.player_slot(&entity)
+        {
+            let target = find_marked_target(
+                &arr_to_vec2(mark_target),
+                obj.side,
+                game_map.as_ref(),
+                game_obj_lib.as_ref(),
+                game_lib.as_ref(),
+            );
+            game_info.set_marked_target(player_slot, target);
+        }
+    }
+}
+
+fn apply_input(
+    entity: Entity,
+    input: &PlayerInput,
+    player_query: &mut Query<
+        (
+            &mut Transform,
+            &mut WeaponComponent,
+            &mut PathComponent,
+            &StatusComponent,
+        ),
+        Or<(With<PlayerComponent>, With<PeerComponent>)>,
+    >,
+    world_info: &WorldInfo,
+    game_map: &GameMap,
+    game_obj_lib: &mut GameObjLib,
+    game_lib: &GameLib,
+    new_obj_queue: &mut NewObjQueue,
+    commands: &mut Commands,
+    time: &Time,
+) {
+    let Ok((mut transform, mut weapon_comp, mut path_comp, status_comp)) =
+        player_query.get_mut(entity)
+    else {
+        return;
+    };
+    let mut input = *input;
+    input.fire &= !status_comp.is_disarmed();
+
+    if apply_player_input(
+        entity,
+        &input,
+        transform.as_mut(),
+        weapon_comp.as_mut(),
+        path_comp.as_mut(),
+        world_info,
+        game_map,
+        game_obj_lib,
+        game_lib,
+        new_obj_queue,
+        commands,
+        time,
+    )
+    .is_err()
+    {
+        error!("Failed to apply player input");
+    }
+}
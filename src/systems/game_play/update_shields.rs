@@ -0,0 +1,33 @@
+use crate::game::{GameObjState, components::ShieldComponent};
+use crate::game_utils::GameObjLib;
+use bevy::prelude::*;
+
+pub fn update_shields(
+    shield_query: Query<(Entity, &ShieldComponent)>,
+    mut visibility_query: Query<&mut Visibility, Without<ShieldComponent>>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    time: Res<Time>,
+) {
+    for (entity, shield_comp) in shield_query.iter() {
+        let Some(obj) = game_obj_lib.get_mut(&entity) else {
+            continue;
+        };
+
+        if obj.state != GameObjState::Alive {
+            continue;
+        }
+
+        let Some(shield) = obj.shield.as_mut() else {
+            continue;
+        };
+        shield.update(time.as_ref());
+
+        if let Ok(mut visibility) = visibility_query.get_mut(shield_comp.sprite) {
+            visibility.set_if_neq(if shield.is_up() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::game::components::SpawnerComponent;
+use crate::game_utils::{GameInfo, GameLib, GameMap, GameObjLib, NewObj, NewObjQueue, WorldInfo};
+use crate::misc::check_collide;
+use bevy::prelude::*;
+
+pub fn update_spawners(
+    mut spawner_query: Query<(Entity, &mut SpawnerComponent)>,
+    mut game_info: ResMut<GameInfo>,
+    world_info: Res<WorldInfo>,
+    game_map: Res<GameMap>,
+    game_obj_lib: Res<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut new_obj_queue: ResMut<NewObjQueue>,
+    time: Res<Time>,
+) {
+    for entity in game_obj_lib.in_spawn_order(spawner_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut spawner_comp)) = spawner_query.get_mut(entity) else {
+            continue;
+        };
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+        let Ok(config) = game_lib
+            .get_game_obj_config(obj.config_index)
+            .spawner_config()
+        else {
+            continue;
+        };
+        let Some(wave) = config.waves.get(spawner_comp.wave_index) else {
+            continue;
+        };
+
+        let Some(bot_name) = spawner_comp.next_bot(wave) else {
+            if spawner_comp.cooldown_timer.tick(time.delta()).is_finished() {
+                // A wave with bots was already counted down by its last bot
+                if spawner_comp.spawn_count == 0 {
+                    let _ = game_info.dec_waves_remaining();
+                }
+                spawner_comp.next_wave();
+            }
+            continue;
+        };
+
+        if !spawner_comp.spawn_timer.tick(time.delta()).is_finished()
+            || game_info.ai_bot_count() + new_obj_queue.ai_bot_count(&game_lib) >= config.max_alive
+        {
+            continue;
+        }
+
+        let Ok(config_index) = game_lib.get_game_obj_config_index(bot_name) else {
+            continue;
+        };
+        let Ok(bot_config) = game_lib.get_game_obj_config(config_index).bot_config() else {
+            continue;
+        };
+
+        // Wait for the previous bot to clear the spawner
+        if check_collide(
+            None,
+            &obj.pos,
+            bot_config.collide_span,
+            game_lib.game_config.max_collide_span,
+            world_info.as_ref(),
+            game_map.as_ref(),
+            game_obj_lib.as_ref(),
+        ) {
+            continue;
+        }
+
+        new_obj_queue.push(NewObj {
+            config_index,
+            pos: obj.pos,
+            direction: obj.direction,
+            speed: None,
+            owner: None,
+        });
+        spawner_comp.spawn_count += 1;
+        spawner_comp.spawn_timer.reset();
+
+        if spawner_comp.next_bot(wave).is_none() {
+            let _ = game_info.dec_waves_remaining();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::config::DamageType;
+use crate::game::{GameObjState, components::StatusComponent, do_damage};
+use crate::game_utils::{GameLib, GameMap, GameObjLib, NewObjQueue};
+use bevy::prelude::*;
+
+pub fn update_status_effects(
+    mut status_query: Query<(Entity, &mut StatusComponent, &mut Sprite)>,
+    game_map: Res<GameMap>,
+    mut game_obj_lib: ResMut<GameObjLib>,
+    game_lib: Res<GameLib>,
+    mut new_obj_queue: ResMut<NewObjQueue>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for entity in game_obj_lib.in_spawn_order(status_query.iter().map(|(entity, ..)| entity)) {
+        let Ok((_, mut status_comp, mut sprite)) = status_query.get_mut(entity) else {
+            continue;
+        };
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+
+        if obj.state != GameObjState::Alive {
+            continue;
+        }
+
+        let damage = status_comp.update(time.as_ref());
+        sprite.color = status_comp.tint();
+
+        if damage <= 0.0 {
+            continue;
+        }
+
+        let _ = do_damage(
+            entity,
+            damage,
+            DamageType::Fire,
+            true,
+            &[],
+            game_map.as_ref(),
+            game_obj_lib.as_mut(),
+            game_lib.as_ref(),
+            new_obj_queue.as_mut(),
+            &mut commands,
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use crate::game::{GameObjState, components::WeaponComponent};
+use crate::game_utils::GameObjLib;
+use crate::misc::get_rotation;
+use bevy::prelude::*;
+
+pub fn update_turrets(
+    mut weapon_query: Query<(Entity, &mut WeaponComponent)>,
+    mut gun_query: Query<&mut Transform, Without<WeaponComponent>>,
+    game_obj_lib: Res<GameObjLib>,
+    time: Res<Time>,
+) {
+    for (entity, mut weapon_comp) in weapon_query.iter_mut() {
+        if weapon_comp.turret.is_none() {
+            continue;
+        }
+
+        let Some(obj) = game_obj_lib.get(&entity) else {
+            continue;
+        };
+
+        if obj.state != GameObjState::Alive {
+            continue;
+        }
+
+        weapon_comp.update_turret(obj.pos, obj.direction, time.as_ref());
+
+        let Some(turret_direction) = weapon_comp.turret.as_ref().map(|turret| turret.direction)
+        else {
+            continue;
+        };
+
+        for (gun, gun_state) in weapon_comp.guns.iter().zip(weapon_comp.gun_states.iter()) {
+            let Ok(mut transform) = gun_query.get_mut(*gun) else {
+                continue;
+            };
+
+            let gun_pos = turret_direction.rotate(gun_state.pos);
+            let gun_direction = turret_direction.rotate(gun_state.fire_direction.normalize());
+            transform.translation.x = gun_pos.x;
+            transform.translation.y = gun_pos.y;
+            transform.rotation = get_rotation(&gun_direction);
+        }
+    }
+}
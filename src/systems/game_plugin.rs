@@ -1,38 +1,96 @@
-use crate::game_utils::{DespawnPool, GameInfo, GameMap, GameObjLib, NewObjQueue, WorldInfo};
+use crate::game::DamageDealt;
+use crate::game_utils::{
+    ActionState, DespawnPool, FogMap, GameInfo, GameMap, GameObjLib, LocalInput, NewObjQueue,
+    WorldInfo,
+};
 use crate::misc::{AppState, GameState};
+use crate::net::NetSession;
 use crate::systems::game_play::{
-    add_new_objs, check_game, cleanup, gameover, process_cursor, process_key, process_mouse_button,
-    setup_game, update_ai, update_ai_bots, update_missiles, update_origin, update_player,
-    update_playout, wait_gameover,
+    add_new_objs, check_game, cleanup, draw_paths, finish_net_tick, gameover, net_tick_ready,
+    pause, process_actions, process_cursor, reset_camera, resize_window, setup_fog, setup_game,
+    setup_hud, setup_minimap, spawn_combat_feedback, sync_net, toggle_pause, update_action_state,
+    update_ai, update_ai_bots, update_beams, update_camera, update_combat_feedback, update_devices,
+    update_fog, update_hud, update_knockback, update_minimap, update_missiles, update_origin,
+    update_player, update_player_input, update_playout, update_shields, update_spawners,
+    update_status_effects, update_turrets, wait_gameover,
 };
-use bevy::prelude::*;
+use bevy::{input::InputSystems, prelude::*, time::TimeUpdateStrategy};
 
 pub fn game_plugin(app: &mut App) {
     app.init_state::<GameState>()
-        .add_systems(OnEnter(AppState::Game), setup_game)
-        .add_systems(PreUpdate, update_ai.run_if(in_state(GameState::Play)))
+        .add_message::<DamageDealt>()
+        .add_systems(
+            OnEnter(AppState::Game),
+            (setup_game, setup_hud, setup_minimap, setup_fog).chain(),
+        )
+        .add_systems(
+            First,
+            sync_net.run_if(in_state(GameState::Play).and(resource_exists::<NetSession>)),
+        )
+        .add_systems(
+            PreUpdate,
+            (
+                update_action_state
+                    .after(InputSystems)
+                    .run_if(in_state(GameState::Play).or(in_state(GameState::Paused))),
+                update_ai.run_if(in_state(GameState::Play).and(net_tick_ready)),
+            ),
+        )
         .add_systems(
             Update,
             (
-                process_cursor,
-                process_key,
-                process_mouse_button,
-                update_ai_bots,
-                update_player,
-                update_missiles,
-                update_playout,
+                (process_cursor, process_actions, update_camera),
+                (
+                    update_status_effects,
+                    update_shields,
+                    update_player_input,
+                    update_turrets,
+                    update_ai_bots,
+                    update_devices,
+                    update_spawners,
+                    update_player,
+                    update_knockback,
+                    update_missiles,
+                    update_playout,
+                    update_beams,
+                )
+                    .chain()
+                    .run_if(net_tick_ready),
+                (
+                    update_hud,
+                    update_minimap,
+                    draw_paths,
+                    (spawn_combat_feedback, update_combat_feedback).chain(),
+                ),
             )
+                .chain()
                 .run_if(in_state(GameState::Play)),
         )
         .add_systems(
             PostUpdate,
-            (update_origin, cleanup, add_new_objs, check_game)
+            (
+                (
+                    update_origin,
+                    cleanup,
+                    add_new_objs,
+                    update_fog.run_if(resource_exists::<FogMap>),
+                    check_game,
+                )
+                    .chain(),
+                finish_net_tick.run_if(resource_exists::<NetSession>),
+            )
                 .chain()
-                .run_if(in_state(GameState::Play)),
+                .run_if(in_state(GameState::Play).and(net_tick_ready)),
+        )
+        .add_systems(
+            Update,
+            (toggle_pause, resize_window)
+                .run_if(in_state(GameState::Play).or(in_state(GameState::Paused))),
         )
+        .add_systems(OnEnter(GameState::Paused), pause)
         .add_systems(OnEnter(GameState::GameOver), gameover)
         .add_systems(Update, wait_gameover.run_if(in_state(GameState::GameOver)))
-        .add_systems(OnExit(AppState::Game), cleanup_res);
+        .add_systems(OnExit(AppState::Game), (cleanup_res, reset_camera));
 }
 
 fn cleanup_res(mut commands: Commands) {
@@ -41,5 +99,10 @@ fn cleanup_res(mut commands: Commands) {
     commands.remove_resource::<DespawnPool>();
     commands.remove_resource::<GameObjLib>();
     commands.remove_resource::<NewObjQueue>();
+    commands.remove_resource::<FogMap>();
     commands.remove_resource::<GameInfo>();
+    commands.remove_resource::<LocalInput>();
+    commands.remove_resource::<ActionState>();
+    commands.remove_resource::<NetSession>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
 }
@@ -2,7 +2,7 @@ use crate::config::{
     GameConfig, GenMapAlgorithmConfig, GenMapConfig, IslandGenMapAlgorithm, NamedGameObjConfig,
 };
 use crate::misc::MyError;
-use crate::systems::gen_map::gen_map_utils::gen_bots;
+use crate::systems::gen_map::gen_map_utils::{gen_bots, gen_spawners};
 use crate::systems::gen_map::generated_map::GeneratedMap;
 use bevy::prelude::*;
 use rand::{Rng, rng, rngs::ThreadRng, seq::SliceRandom};
@@ -13,6 +13,7 @@ pub fn gen_island_map(
     player_config: &NamedGameObjConfig,
     ai_bot_configs: &Vec<NamedGameObjConfig>,
     tile_configs: &Vec<NamedGameObjConfig>,
+    spawner_configs: &Vec<NamedGameObjConfig>,
 ) -> Result<GeneratedMap, MyError> {
     let GenMapAlgorithmConfig::Island(config) = &gen_map_config.algorithm else {
         let msg = "gen_map_config doesn't have IslandGenMapAlgorithm".to_string();
@@ -26,6 +27,11 @@ pub fn gen_island_map(
     );
 
     gen_islands(&mut map, config, tile_configs)?;
+    gen_spawners(
+        &mut map,
+        gen_map_config.spawner_count.unwrap_or(0),
+        spawner_configs,
+    )?;
     gen_bots(
         &mut map,
         gen_map_config.ai_bot_count,
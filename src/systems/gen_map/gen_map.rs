@@ -15,7 +15,7 @@ pub fn gen_map(args: Args) {
     else {
         return;
     };
-    let Some((player_config, ai_bot_configs, tile_configs)) =
+    let Some((player_config, ai_bot_configs, tile_configs, spawner_configs)) =
         extract_obj_configs(&game_obj_configs)
     else {
         return;
@@ -28,6 +28,7 @@ pub fn gen_map(args: Args) {
             &player_config,
             &ai_bot_configs,
             &tile_configs,
+            &spawner_configs,
         ),
         _ => {
             error!("Unsupported algorithm for generating map");
@@ -106,10 +107,12 @@ fn extract_obj_configs(
     NamedGameObjConfig,
     Vec<NamedGameObjConfig>,
     Vec<NamedGameObjConfig>,
+    Vec<NamedGameObjConfig>,
 )> {
     let mut player_config: Option<NamedGameObjConfig> = None;
     let mut ai_bot_configs: Vec<NamedGameObjConfig> = Vec::new();
     let mut tile_configs: Vec<NamedGameObjConfig> = Vec::new();
+    let mut spawner_configs: Vec<NamedGameObjConfig> = Vec::new();
 
     for obj_config in obj_configs.iter() {
         match &obj_config.config {
@@ -125,6 +128,9 @@ fn extract_obj_configs(
             GameObjConfig::Tile(_) => {
                 tile_configs.push(obj_config.clone());
             }
+            GameObjConfig::Spawner(_) => {
+                spawner_configs.push(obj_config.clone());
+            }
             _ => {}
         }
     }
@@ -147,5 +153,5 @@ fn extract_obj_configs(
         return None;
     }
 
-    Some((player_config, ai_bot_configs, tile_configs))
+    Some((player_config, ai_bot_configs, tile_configs, spawner_configs))
 }
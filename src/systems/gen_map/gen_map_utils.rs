@@ -57,6 +57,45 @@ pub fn gen_bots(
     Ok(())
 }
 
+// Spawners go in first so that bots are not placed on top of them
+pub fn gen_spawners(
+    map: &mut GeneratedMap,
+    spawner_count: usize,
+    spawner_configs: &Vec<NamedGameObjConfig>,
+) -> Result<(), MyError> {
+    if spawner_count == 0 {
+        return Ok(());
+    }
+
+    if spawner_configs.is_empty() {
+        let msg = "spawner_configs is empty".to_string();
+        error!(msg);
+        return Err(MyError::Other(msg));
+    }
+
+    let mut max_size = 0.0;
+    for config in spawner_configs.iter() {
+        let spawner_config = config.spawner_config()?;
+        max_size = spawner_config.size[0]
+            .max(spawner_config.size[1])
+            .max(max_size);
+    }
+
+    let mut spots = get_candidate_spots_for_bots(map, max_size)?;
+    let mut r = rng();
+    spots.shuffle(&mut r);
+
+    for _ in 0..spawner_count {
+        let Some(pos) = spots.pop() else {
+            break;
+        };
+        let spawner_config = spawner_configs.choose(&mut r).unwrap();
+        map.add(pos, Vec2::new(1.0, 0.0), spawner_config.clone())?;
+    }
+
+    Ok(())
+}
+
 pub fn write_gen_map(map: &GeneratedMap, file_path: &PathBuf) -> bool {
     let map_config = map.to_map_config();
     let file = match File::create(file_path) {
@@ -110,7 +149,7 @@ fn get_candidate_spots_for_bots(
         while x < width {
             let pos = Vec2::new(x, y);
 
-            if !check_collide_tile(map, &pos, half_size)? {
+            if !check_collide_static(map, &pos, half_size)? {
                 spots.push(pos);
             }
 
@@ -123,7 +162,12 @@ fn get_candidate_spots_for_bots(
     Ok(spots)
 }
 
-fn check_collide_tile(map: &GeneratedMap, pos: &Vec2, collide_span: f32) -> Result<bool, MyError> {
+// Tiles and spawners
+fn check_collide_static(
+    map: &GeneratedMap,
+    pos: &Vec2,
+    collide_span: f32,
+) -> Result<bool, MyError> {
     let region = map.get_map_region(
         pos.x - collide_span,
         pos.y - collide_span,
@@ -134,11 +178,15 @@ fn check_collide_tile(map: &GeneratedMap, pos: &Vec2, collide_span: f32) -> Resu
     for row in region.start_row..=region.end_row {
         for col in region.start_col..=region.end_col {
             for item in map.get_cell(row, col).iter() {
-                let GameObjConfig::Tile(tile_config) = &(item.config.config) else {
-                    continue;
+                let item_span = match &item.config.config {
+                    GameObjConfig::Tile(tile_config) => tile_config.collide_span,
+                    GameObjConfig::Spawner(spawner_config) => {
+                        spawner_config.size[0].max(spawner_config.size[1]) / 2.0
+                    }
+                    _ => continue,
                 };
 
-                if check_collide_obj(pos, collide_span, &item.pos, tile_config.collide_span) {
+                if check_collide_obj(pos, collide_span, &item.pos, item_span) {
                     return Ok(true);
                 }
             }